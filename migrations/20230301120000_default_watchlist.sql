@@ -0,0 +1,8 @@
+-- Map the former registrar singleton onto the default watchlist
+UPDATE events
+   SET aggregate_id = 'default'
+ WHERE aggregate_type = 'registrar' AND aggregate_id = '<singleton>';
+
+UPDATE monitored_zones_query
+   SET view_id = 'default'
+ WHERE view_id = '<singleton>';
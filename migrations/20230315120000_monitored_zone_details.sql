@@ -0,0 +1,25 @@
+-- Restructure monitored zones from a code array into per-zone entries, typing each zone as implied
+-- by its code: `C` codes are counties, codes in a marine area are marine zones, and the rest are
+-- forecast zones.
+UPDATE monitored_zones_query
+   SET payload = json_build_object(
+         'zones',
+         COALESCE(
+           (SELECT json_object_agg(
+                     zone,
+                     json_build_object(
+                       'zoneType',
+                       CASE
+                         WHEN substr(zone, 3, 1) = 'C' THEN 'County'
+                         WHEN substr(zone, 1, 2) IN ('AM', 'AN', 'GM', 'LC', 'LE', 'LH', 'LM', 'LO',
+                                                     'LS', 'PH', 'PK', 'PM', 'PS', 'PZ', 'SL')
+                           THEN 'Marine'
+                         ELSE 'Forecast'
+                       END
+                     )
+                   )
+              FROM json_array_elements_text(payload -> 'zones') AS zone),
+           '{}'::json
+         )
+       )
+ WHERE json_typeof(payload -> 'zones') = 'array';
@@ -0,0 +1,18 @@
+-- Create observation_history table holding every observation frame recorded for a zone
+CREATE TABLE observation_history(
+  zone_code   text                          NOT NULL,
+  observed_at timestamptz                   NOT NULL,
+  sequence    bigint CHECK (sequence >= 0)  NOT NULL,
+  payload     json                          NOT NULL,
+  PRIMARY KEY (zone_code, observed_at)
+);
+
+-- Backfill from the observations already recorded in location zone events
+INSERT INTO observation_history (zone_code, observed_at, sequence, payload)
+SELECT aggregate_id,
+       (payload -> 'ObservationAdded' ->> 'timestamp')::timestamptz,
+       sequence,
+       payload -> 'ObservationAdded'
+  FROM events
+ WHERE aggregate_type = 'location_zone' AND event_type = 'observation_added'
+    ON CONFLICT (zone_code, observed_at) DO NOTHING;
@@ -0,0 +1,11 @@
+-- Weather views list every active alert rather than a single alert
+UPDATE weather_query
+   SET payload = (
+         (payload::jsonb - 'alert')
+         || jsonb_build_object('alerts', jsonb_build_array(payload::jsonb -> 'alert'))
+       )::json
+ WHERE payload::jsonb -> 'alert' IS NOT NULL AND payload::jsonb -> 'alert' <> 'null'::jsonb;
+
+UPDATE weather_query
+   SET payload = (payload::jsonb - 'alert')::json
+ WHERE payload::jsonb ? 'alert';
@@ -0,0 +1,11 @@
+-- Create snapshots table holding the latest snapshot of each aggregate, from which the aggregate is
+-- loaded before applying only its later events
+CREATE TABLE snapshots(
+  aggregate_type   text                                    NOT NULL,
+  aggregate_id     text                                    NOT NULL,
+  last_sequence    bigint CHECK (last_sequence >= 0)       NOT NULL,
+  current_snapshot bigint CHECK (current_snapshot >= 0)    NOT NULL,
+  payload          json                                    NOT NULL,
+  timestamp        timestamp with time zone DEFAULT (CURRENT_TIMESTAMP),
+  PRIMARY KEY (aggregate_type, aggregate_id)
+);
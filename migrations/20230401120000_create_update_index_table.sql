@@ -0,0 +1,62 @@
+-- Create update_index table indexing every update locations saga by start time, status and zones
+CREATE TABLE update_index(
+  update_id     text                                 NOT NULL,
+  started_at    timestamptz                          NOT NULL,
+  finished_at   timestamptz,
+  status        text                                 NOT NULL,
+  zones         text[]                               NOT NULL,
+  zone_count    integer CHECK (zone_count >= 0)      NOT NULL,
+  failure_count integer CHECK (failure_count >= 0)   NOT NULL DEFAULT 0,
+  watchlist     text,
+  PRIMARY KEY (update_id)
+);
+
+CREATE INDEX update_index_started_at_idx ON update_index (started_at DESC);
+CREATE INDEX update_index_status_idx ON update_index (status, started_at DESC);
+CREATE INDEX update_index_zones_idx ON update_index USING GIN (zones);
+
+-- Backfill from the updates already recorded in update_locations_query and their saga events.
+-- Updates started before event occurrence times were recorded are indexed as started at the epoch.
+INSERT INTO update_index (update_id, started_at, finished_at, status, zones, zone_count, failure_count, watchlist)
+SELECT updates.view_id,
+       COALESCE((started.metadata ->> 'occurred_at')::timestamptz, 'epoch'::timestamptz),
+       (finished.metadata ->> 'occurred_at')::timestamptz,
+       CASE finished.event_type
+         WHEN 'completed' THEN 'succeeded'
+         WHEN 'failed' THEN 'failed'
+         WHEN 'partially_succeeded' THEN 'partially_succeeded'
+         WHEN 'timed_out' THEN 'timed_out'
+         WHEN 'cancelled' THEN 'cancelled'
+         ELSE 'running'
+       END,
+       ARRAY(SELECT json_array_elements_text(started.zones)),
+       COALESCE(json_array_length(started.zones), 0),
+       (SELECT count(*)
+          FROM events failed
+         WHERE failed.aggregate_type = 'update_locations'
+           AND failed.aggregate_id = updates.view_id
+           AND failed.event_type = 'location_updated'
+           AND failed.payload -> 'LocationUpdated' -> 1 ->> 'Right' = 'Failed')
+       + COALESCE((SELECT count(*) FROM json_object_keys(finished.payload -> 'TimedOut')), 0),
+       COALESCE(updates.payload ->> 'watchlist', started.metadata ->> 'watchlist')
+  FROM update_locations_query updates
+  JOIN LATERAL (
+        SELECT metadata,
+               COALESCE(payload -> 'StartedSteps' -> 1, payload -> 'Started' -> 1) AS zones
+          FROM events
+         WHERE aggregate_type = 'update_locations'
+           AND aggregate_id = updates.view_id
+           AND event_type IN ('started_steps', 'started')
+         ORDER BY sequence
+         LIMIT 1
+       ) started ON true
+  LEFT JOIN LATERAL (
+        SELECT event_type, payload, metadata
+          FROM events
+         WHERE aggregate_type = 'update_locations'
+           AND aggregate_id = updates.view_id
+           AND event_type IN ('completed', 'failed', 'partially_succeeded', 'timed_out', 'cancelled')
+         ORDER BY sequence
+         LIMIT 1
+       ) finished ON true
+ON CONFLICT (update_id) DO NOTHING;
@@ -0,0 +1,11 @@
+-- Create zone_update_summaries table recording the latest update of each zone per watchlist,
+-- apart from the monitored zones view.
+CREATE TABLE zone_update_summaries(
+  watchlist            text                                        NOT NULL,
+  zone_code            text                                        NOT NULL,
+  saga_id              text                                        NOT NULL,
+  status               text,
+  completed_at         timestamptz,
+  consecutive_failures integer CHECK (consecutive_failures >= 0)   NOT NULL DEFAULT 0,
+  PRIMARY KEY (watchlist, zone_code)
+);
@@ -9,5 +9,5 @@ mod services;
 mod settings;
 pub mod tracing;
 
-pub use server::Server;
+pub use server::{rebuild_snapshots, Server};
 pub use settings::{CliOptions, Settings};
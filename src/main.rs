@@ -16,6 +16,11 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("settings = {settings:?}");
     let settings = settings?;
 
+    if options.rebuild_snapshots {
+        tracing::info!("rebuilding aggregate snapshots...");
+        return weather::rebuild_snapshots(&settings).await.map_err(|err| err.into());
+    }
+
     let server = weather::Server::build(&settings).await?;
     tracing::info!(?server, "starting server...");
     server.run_until_stopped().await.map_err(|err| err.into())
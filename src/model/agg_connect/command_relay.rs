@@ -1,8 +1,9 @@
-use super::CommandEnvelope;
-use cqrs_es::{Aggregate, CqrsFramework, EventStore};
+use super::{with_occurred_at, CommandEnvelope, CORRELATION_METADATA};
+use cqrs_es::{Aggregate, AggregateError, CqrsFramework, EventStore};
+use std::collections::HashSet;
 use std::fmt::{self, Debug};
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
 
 pub struct CommandRelay<A, ES>
@@ -13,6 +14,54 @@ where
 {
     command_rx: mpsc::Receiver<CommandEnvelope<A>>,
     aggregate: Arc<CqrsFramework<A, ES>>,
+    failure_tx: Option<mpsc::Sender<CommandFailure<A>>>,
+    cancelled: Option<CancelledCorrelations>,
+}
+
+/// Correlations whose relayed commands are cancelled, shared between the process cancelling its
+/// work and the relays dropping the cancelled commands. A correlation is forgotten once its
+/// process has finished and no more of its commands are expected.
+#[derive(Debug, Clone, Default)]
+pub struct CancelledCorrelations(Arc<RwLock<HashSet<String>>>);
+
+impl CancelledCorrelations {
+    pub async fn cancel(&self, correlation: impl Into<String>) {
+        self.0.write().await.insert(correlation.into());
+    }
+
+    pub async fn is_cancelled(&self, correlation: &str) -> bool {
+        self.0.read().await.contains(correlation)
+    }
+
+    pub async fn forget(&self, correlation: &str) {
+        self.0.write().await.remove(correlation);
+    }
+}
+
+/// Relayed command the aggregate failed to execute, keyed by the correlation in the command's
+/// metadata so the failure can be routed back to the process that issued the command.
+pub struct CommandFailure<A>
+where
+    A: Aggregate,
+    A::Command: Debug,
+{
+    pub correlation: String,
+    pub command: CommandEnvelope<A>,
+    pub error: AggregateError<A::Error>,
+}
+
+impl<A> fmt::Debug for CommandFailure<A>
+where
+    A: Aggregate,
+    A::Command: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandFailure")
+            .field("correlation", &self.correlation)
+            .field("command", &self.command)
+            .field("error", &self.error.to_string())
+            .finish()
+    }
 }
 
 impl<A, ES> fmt::Debug for CommandRelay<A, ES>
@@ -35,7 +84,25 @@ where
     pub fn new(
         aggregate: Arc<CqrsFramework<A, ES>>, command_rx: mpsc::Receiver<CommandEnvelope<A>>,
     ) -> Self {
-        Self { command_rx, aggregate }
+        Self {
+            command_rx,
+            aggregate,
+            failure_tx: None,
+            cancelled: None,
+        }
+    }
+
+    /// Sends the failures of relayed commands carrying a correlation in their metadata to the
+    /// channel. Failures of uncorrelated commands are only logged.
+    pub fn with_failure_tx(mut self, failure_tx: mpsc::Sender<CommandFailure<A>>) -> Self {
+        self.failure_tx = Some(failure_tx);
+        self
+    }
+
+    /// Drops relayed commands whose correlation is cancelled rather than executing them.
+    pub fn with_cancellations(mut self, cancelled: CancelledCorrelations) -> Self {
+        self.cancelled = Some(cancelled);
+        self
     }
 }
 
@@ -52,7 +119,17 @@ where
 
     async fn do_run(mut self) {
         while let Some(command) = self.command_rx.recv().await {
+            if self.is_cancelled(&command).await {
+                tracing::info!(
+                    ?command,
+                    "dropped cancelled command to {}",
+                    A::aggregate_type()
+                );
+                continue;
+            }
+
             let (agg_id, cmd, meta) = command.as_parts();
+            let meta = with_occurred_at(meta);
             match self.aggregate.execute_with_metadata(&agg_id, cmd, meta).await {
                 Ok(()) => tracing::debug!(?command, "command relayed to {}", A::aggregate_type()),
                 Err(error) => {
@@ -61,9 +138,39 @@ where
                         ?command,
                         "failed to relay command to {}",
                         A::aggregate_type()
-                    )
+                    );
+                    self.do_send_failure(command, error).await;
                 },
             }
         }
     }
+
+    async fn is_cancelled(&self, command: &CommandEnvelope<A>) -> bool {
+        let correlation = command.metadata().get(CORRELATION_METADATA);
+        match (&self.cancelled, correlation) {
+            (Some(cancelled), Some(correlation)) => cancelled.is_cancelled(correlation).await,
+            _ => false,
+        }
+    }
+
+    async fn do_send_failure(&self, command: CommandEnvelope<A>, error: AggregateError<A::Error>) {
+        let failure_tx = match &self.failure_tx {
+            Some(tx) => tx,
+            None => return,
+        };
+
+        let correlation = match command.metadata().get(CORRELATION_METADATA) {
+            Some(correlation) => correlation.clone(),
+            None => return,
+        };
+
+        let failure = CommandFailure { correlation, command, error };
+        if let Err(error) = failure_tx.send(failure).await {
+            tracing::error!(
+                ?error,
+                "failed to send command failure from {} relay",
+                A::aggregate_type()
+            );
+        }
+    }
 }
@@ -1,14 +1,41 @@
 mod command_relay;
 mod event_broadcast;
 
-pub use command_relay::CommandRelay;
+pub use command_relay::{CancelledCorrelations, CommandFailure, CommandRelay};
 pub use event_broadcast::{EventBroadcastQuery, EventSubscriber, SubscribeCommand};
 
+use chrono::{DateTime, Utc};
 use cqrs_es::Aggregate;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::sync::Arc;
 
+/// Metadata key correlating a command with the process, such as an update saga, that issued it.
+pub const CORRELATION_METADATA: &str = "correlation";
+
+/// Metadata key recording when a command was executed, and so when its events occurred, as an
+/// RFC 3339 timestamp.
+pub const OCCURRED_AT_METADATA: &str = "occurred_at";
+
+/// Stamps the command metadata with the current time as the command's occurrence.
+pub fn with_occurred_at(mut metadata: HashMap<String, String>) -> HashMap<String, String> {
+    metadata.insert(OCCURRED_AT_METADATA.to_string(), Utc::now().to_rfc3339());
+    metadata
+}
+
+/// Command metadata recording only the command's occurrence at the current time.
+pub fn occurrence_metadata() -> HashMap<String, String> {
+    with_occurred_at(HashMap::new())
+}
+
+/// When the event occurred, per its command's metadata; else now.
+pub fn occurred_at(metadata: &HashMap<String, String>) -> DateTime<Utc> {
+    metadata
+        .get(OCCURRED_AT_METADATA)
+        .and_then(|occurred_at| DateTime::parse_from_rfc3339(occurred_at).ok())
+        .map_or_else(Utc::now, |occurred_at| occurred_at.with_timezone(&Utc))
+}
+
 pub struct EventEnvelope<A: Aggregate> {
     inner: Arc<EventEnvelopeRef<A>>,
 }
@@ -4,7 +4,7 @@ use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::num::TryFromIntError;
 use strum::{IntoEnumIterator, VariantNames};
 use strum_macros::{Display, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
@@ -64,16 +64,137 @@ pub struct WeatherFrame {
     pub heat_index: Option<QuantitativeValue>,
 }
 
+impl WeatherFrame {
+    pub fn property(&self, q_prop: QuantitativeProperty) -> Option<&QuantitativeValue> {
+        use QuantitativeProperty as Q;
+
+        match q_prop {
+            Q::Temperature => self.temperature.as_ref(),
+            Q::Dewpoint => self.dewpoint.as_ref(),
+            Q::WindDirection => self.wind_direction.as_ref(),
+            Q::WindSpeed => self.wind_speed.as_ref(),
+            Q::WindGust => self.wind_gust.as_ref(),
+            Q::BarometricPressure => self.barometric_pressure.as_ref(),
+            Q::SeaLevelPressure => self.sea_level_pressure.as_ref(),
+            Q::Visibility => self.visibility.as_ref(),
+            Q::MaxTemperatureLast24Hours => self.max_temperature_last_24_hours.as_ref(),
+            Q::MinTemperatureLast24Hours => self.min_temperature_last_24_hours.as_ref(),
+            Q::PrecipitationLastHour => self.precipitation_last_hour.as_ref(),
+            Q::PrecipitationLast3Hours => self.precipitation_last_3_hours.as_ref(),
+            Q::PrecipitationLast6Hours => self.precipitation_last_6_hours.as_ref(),
+            Q::RelativeHumidity => self.relative_humidity.as_ref(),
+            Q::WindChill => self.wind_chill.as_ref(),
+            Q::HeatIndex => self.heat_index.as_ref(),
+        }
+    }
+}
+
+/// Largest change in each property's value still considered the same reading. Properties without
+/// a tolerance must match exactly.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ObservationTolerances(HashMap<QuantitativeProperty, f32>);
+
+impl ObservationTolerances {
+    pub fn new(tolerances: impl IntoIterator<Item = (QuantitativeProperty, f32)>) -> Self {
+        Self(tolerances.into_iter().collect())
+    }
+
+    pub fn tolerance(&self, q_prop: QuantitativeProperty) -> f32 {
+        self.0.get(&q_prop).copied().unwrap_or(0.0)
+    }
+
+    /// Whether the observation repeats the prior readings, within tolerance. The frame timestamps
+    /// are not compared.
+    pub fn is_unchanged(&self, prior: &WeatherFrame, observation: &WeatherFrame) -> bool {
+        QuantitativeProperty::iter().all(|q_prop| {
+            match (prior.property(q_prop), observation.property(q_prop)) {
+                (None, None) => true,
+                (Some(p), Some(o)) => {
+                    p.unit_code == o.unit_code
+                        && (p.value - o.value).abs() <= self.tolerance(q_prop)
+                },
+                _ => false,
+            }
+        })
+    }
+}
+
 impl From<FeatureCollection> for WeatherFrame {
     fn from(geojson: FeatureCollection) -> Self {
         geojson
             .features
-            .into_iter()
+            .iter()
             .fold(PropertyAggregations::new(), fold_feature)
             .into()
     }
 }
 
+/// A zone's observation aggregated across its stations, along with the latest reading of each
+/// station so a questionable reading can be traced to the station that sent it.
+#[derive(Debug, PartialEq, Clone, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneObservation {
+    pub frame: WeatherFrame,
+
+    /// Latest reading of each reporting station, keyed by station identifier.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stations: BTreeMap<String, WeatherFrame>,
+}
+
+impl From<FeatureCollection> for ZoneObservation {
+    fn from(geojson: FeatureCollection) -> Self {
+        let mut latest: BTreeMap<String, (Timestamp, &Feature)> = BTreeMap::new();
+        for feature in geojson.features.iter() {
+            let (station, timestamp) = match (station_id(feature), observed_at(feature)) {
+                (Some(station), Some(timestamp)) => (station, timestamp),
+                _ => continue,
+            };
+
+            match latest.get(&station) {
+                Some((prior, _)) if timestamp <= *prior => (),
+                _ => {
+                    latest.insert(station, (timestamp, feature));
+                },
+            }
+        }
+
+        let stations = latest
+            .into_iter()
+            .map(|(station, (timestamp, feature))| {
+                let frame = fold_feature(PropertyAggregations::at(timestamp), feature).into();
+                (station, frame)
+            })
+            .collect();
+
+        let frame = geojson
+            .features
+            .iter()
+            .fold(PropertyAggregations::new(), fold_feature)
+            .into();
+        Self { frame, stations }
+    }
+}
+
+/// Identifies the station reporting the observation from its station url, e.g.,
+/// `https://api.weather.gov/stations/KSEA`.
+fn station_id(feature: &Feature) -> Option<String> {
+    feature
+        .property("station")
+        .and_then(|station| station.as_str())
+        .and_then(|url| url.trim_end_matches('/').rsplit('/').next())
+        .or_else(|| feature.property("stationId").and_then(|id| id.as_str()))
+        .filter(|id| !id.is_empty())
+        .map(|id| id.to_string())
+}
+
+fn observed_at(feature: &Feature) -> Option<Timestamp> {
+    feature
+        .property("timestamp")
+        .and_then(|timestamp| timestamp.as_str())
+        .and_then(Timestamp::parse)
+}
+
 #[derive(Debug)]
 struct PropertyAggregations {
     timestamp: Timestamp,
@@ -82,8 +203,12 @@ struct PropertyAggregations {
 
 impl PropertyAggregations {
     pub fn new() -> Self {
+        Self::at(Timestamp::now_utc())
+    }
+
+    pub fn at(timestamp: Timestamp) -> Self {
         Self {
-            timestamp: Timestamp::now_utc(),
+            timestamp,
             properties: HashMap::with_capacity(QuantitativeProperty::VARIANTS.len()),
         }
     }
@@ -137,7 +262,7 @@ impl From<PropertyAggregations> for WeatherFrame {
 }
 
 // #[tracing::instrument(level = "trace", skip(feature))]
-fn fold_feature(mut acc: PropertyAggregations, feature: Feature) -> PropertyAggregations {
+fn fold_feature(mut acc: PropertyAggregations, feature: &Feature) -> PropertyAggregations {
     if feature.properties.is_none() {
         return acc;
     }
@@ -1,17 +1,22 @@
 mod agg_connect;
 mod frame;
 pub mod registrar;
+pub mod scheduler;
+pub mod snapshots;
 mod tracing_query;
+mod upcasting;
 pub mod update;
 pub mod zone;
 
 pub use agg_connect::{
-    CommandEnvelope, CommandRelay, EventBroadcastQuery, EventEnvelope, EventSubscriber,
-    SubscribeCommand,
+    occurred_at, occurrence_metadata, with_occurred_at, CancelledCorrelations, CommandEnvelope,
+    CommandFailure, CommandRelay, EventBroadcastQuery, EventEnvelope, EventSubscriber,
+    SubscribeCommand, CORRELATION_METADATA, OCCURRED_AT_METADATA,
 };
-pub use frame::WeatherFrame;
+pub use frame::{ObservationTolerances, QuantitativeProperty, WeatherFrame, ZoneObservation};
 pub use registrar::{Registrar, RegistrarAggregate};
 pub use tracing_query::TracingQuery;
+pub use upcasting::{EventUpcasterRegistry, VersionedEvent};
 pub use update::{UpdateLocations, UpdateLocationsSaga};
 pub use zone::{LocationZone, LocationZoneAggregate};
 
@@ -74,6 +79,30 @@ impl LocationZoneCode {
         Self(code.into())
     }
 
+    /// Checks the code has the form of a NWS zone identifier: a two-letter state or marine area,
+    /// a `Z` (public, forecast or fire zone) or `C` (county), and a three-digit number; e.g.,
+    /// `WAZ558` or `WAC033`.
+    pub fn is_well_formed(&self) -> bool {
+        let code = self.0.as_bytes();
+        code.len() == 6
+            && code[..2].iter().all(u8::is_ascii_uppercase)
+            && matches!(code[2], b'Z' | b'C')
+            && code[3..].iter().all(u8::is_ascii_digit)
+    }
+
+    /// The zone type implied by the code: `C` codes identify counties, `Z` codes in a marine
+    /// area identify marine zones, and other `Z` codes are treated as forecast zones.
+    pub fn implied_zone_type(&self) -> LocationZoneType {
+        let code = self.0.as_bytes();
+        match code.get(2) {
+            Some(b'C') => LocationZoneType::County,
+            _ if MARINE_AREAS.iter().any(|area| code.starts_with(area.as_bytes())) => {
+                LocationZoneType::Marine
+            },
+            _ => LocationZoneType::Forecast,
+        }
+    }
+
     pub fn from_url(url: impl Into<Url>) -> Result<(Option<LocationZoneType>, Self), WeatherError> {
         let url = url.into();
         url.path_segments()
@@ -120,12 +149,32 @@ impl AsRef<str> for LocationZoneCode {
     Serialize,
     Deserialize,
 )]
+#[strum(ascii_case_insensitive)]
 pub enum LocationZoneType {
     Public,
     County,
     Forecast,
+    Marine,
+}
+
+impl LocationZoneType {
+    /// The zone type as named in NWS zone paths; e.g., `/zones/forecast/WAZ558`.
+    pub const fn nws_path_segment(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::County => "county",
+            Self::Forecast => "forecast",
+            Self::Marine => "marine",
+        }
+    }
 }
 
+/// Two-letter prefixes of NWS marine zone codes, which name coastal waters, the Great Lakes and
+/// other marine areas rather than states; e.g., `PZZ135` or `LMZ741`.
+const MARINE_AREAS: [&str; 15] = [
+    "AM", "AN", "GM", "LC", "LE", "LH", "LM", "LO", "LS", "PH", "PK", "PM", "PS", "PZ", "SL",
+];
+
 // #[derive(
 //     Debug,
 //     Copy,
@@ -335,6 +384,37 @@ impl TryFrom<Feature> for ZoneForecast {
     }
 }
 
+/// Description of a zone, as published by the weather provider's zone metadata endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneMetadata {
+    pub zone_code: LocationZoneCode,
+    pub zone_type: LocationZoneType,
+    pub name: String,
+
+    /// Marine and offshore zones do not belong to a state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+impl ZoneMetadata {
+    pub fn from_feature(
+        zone_type: LocationZoneType, feature: &Feature,
+    ) -> Result<Self, WeatherError> {
+        let extract = PropertyExtractor::new("zone_metadata", feature);
+
+        Ok(Self {
+            zone_code: LocationZoneCode::new(extract.property::<String>("id")?),
+            zone_type,
+            name: extract.property("name")?,
+            state: feature
+                .property("state")
+                .and_then(|state| state.as_str())
+                .map(String::from),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ForecastDetail {
@@ -347,6 +427,11 @@ pub struct ForecastDetail {
 #[derive(Debug, Clone, PartialEq, ToSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WeatherAlert {
+    /// The CAP identifier of the alert message. Alerts recorded before the identifier was
+    /// captured share an empty identifier.
+    #[serde(default)]
+    pub id: String,
+
     pub affected_zones: Vec<LocationZoneCode>,
     pub status: AlertStatus,
     pub message_type: AlertMessageType,
@@ -396,14 +481,33 @@ pub struct WeatherAlert {
     pub response: AlertResponse,
 }
 
+impl WeatherAlert {
+    /// Whether the alert message has expired, or its subject event has ended, as of the time.
+    pub fn is_expired(&self, as_of: DateTime<Utc>) -> bool {
+        self.expires <= as_of || self.ends.map_or(false, |ends| ends <= as_of)
+    }
+
+    /// Orders alerts most severe first, then most urgent.
+    pub fn priority_cmp(&self, other: &Self) -> Ordering {
+        (self.severity, self.urgency, &self.id).cmp(&(other.severity, other.urgency, &other.id))
+    }
+}
+
 impl TryFrom<Feature> for WeatherAlert {
     type Error = WeatherError;
 
     fn try_from(f: Feature) -> Result<Self, Self::Error> {
         let extract = PropertyExtractor::new("weather_alert", &f);
 
+        // NWS lists affected zones by URL; e.g., https://api.weather.gov/zones/county/MDC031
+        let affected_zones = extract
+            .property::<Vec<Url>>("affectedZones")?
+            .into_iter()
+            .map(|url| LocationZoneCode::from_url(url).map(|(_, zone_code)| zone_code));
+
         Ok(Self {
-            affected_zones: extract.property("affectedZones")?,
+            id: extract.property("id")?,
+            affected_zones: transpose_result(affected_zones)?,
             status: extract.property("status")?,
             message_type: extract.property("status")?,
             sent: extract.property("sent")?,
@@ -523,12 +627,15 @@ pub enum AlertCategory {
     Other,
 }
 
+/// Declared most severe first; alerts are ordered by this declaration order.
 #[derive(
     Debug,
     Copy,
     Clone,
     PartialEq,
     Eq,
+    PartialOrd,
+    Ord,
     Hash,
     Display,
     EnumString,
@@ -571,12 +678,15 @@ pub enum AlertCertainty {
     Unknown,
 }
 
+/// Declared most urgent first; alerts are ordered by this declaration order.
 #[derive(
     Debug,
     Copy,
     Clone,
     PartialEq,
     Eq,
+    PartialOrd,
+    Ord,
     Hash,
     Display,
     EnumString,
@@ -701,7 +811,7 @@ pub fn average_direction(directions: &[Direction]) -> Option<Direction> {
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
-    use claim::assert_some;
+    use claim::{assert_err, assert_ok, assert_some};
     use pretty_assertions::assert_eq;
     use proptest::collection::vec;
     use proptest::prelude::*;
@@ -722,6 +832,96 @@ mod tests {
     //     }
     // }
 
+    #[test]
+    fn test_location_zone_code_is_well_formed() {
+        assert!(LocationZoneCode::new("WAZ558").is_well_formed());
+        assert!(LocationZoneCode::new("WAC033").is_well_formed());
+        assert!(!LocationZoneCode::new("waz558").is_well_formed());
+        assert!(!LocationZoneCode::new("WAX558").is_well_formed());
+        assert!(!LocationZoneCode::new("WAZ5581").is_well_formed());
+        assert!(!LocationZoneCode::new("").is_well_formed());
+    }
+
+    #[test]
+    fn test_location_zone_code_implied_zone_type() {
+        assert_eq!(
+            LocationZoneCode::new("WAZ558").implied_zone_type(),
+            LocationZoneType::Forecast
+        );
+        assert_eq!(
+            LocationZoneCode::new("WAC033").implied_zone_type(),
+            LocationZoneType::County
+        );
+        assert_eq!(
+            LocationZoneCode::new("PZZ135").implied_zone_type(),
+            LocationZoneType::Marine
+        );
+        assert_eq!(
+            LocationZoneCode::new("LMZ741").implied_zone_type(),
+            LocationZoneType::Marine
+        );
+    }
+
+    #[test]
+    fn test_location_zone_type_names() {
+        assert_eq!(LocationZoneType::Forecast.to_string(), "Forecast");
+        assert_eq!(LocationZoneType::Forecast.nws_path_segment(), "forecast");
+        assert_eq!(
+            LocationZoneType::from_str("marine"),
+            Ok(LocationZoneType::Marine)
+        );
+    }
+
+    #[test]
+    fn test_zone_metadata_from_feature() {
+        let feature = |properties: serde_json::Value| -> Feature {
+            serde_json::from_value(serde_json::json!({
+                "type": "Feature",
+                "geometry": null,
+                "properties": properties,
+            }))
+            .unwrap()
+        };
+
+        let forecast_zone = feature(serde_json::json!({
+            "id": "WAZ558",
+            "type": "public",
+            "name": "Seattle and Vicinity",
+            "state": "WA",
+        }));
+        let actual = assert_ok!(ZoneMetadata::from_feature(
+            LocationZoneType::Forecast,
+            &forecast_zone
+        ));
+        assert_eq!(
+            actual,
+            ZoneMetadata {
+                zone_code: LocationZoneCode::new("WAZ558"),
+                zone_type: LocationZoneType::Forecast,
+                name: "Seattle and Vicinity".to_string(),
+                state: Some("WA".to_string()),
+            }
+        );
+
+        let marine_zone = feature(serde_json::json!({
+            "id": "PZZ135",
+            "type": "marine",
+            "name": "Puget Sound and Hood Canal",
+            "state": null,
+        }));
+        let actual = assert_ok!(ZoneMetadata::from_feature(
+            LocationZoneType::Marine,
+            &marine_zone
+        ));
+        assert_eq!(actual.state, None);
+
+        let unnamed = feature(serde_json::json!({ "id": "WAZ558" }));
+        assert_err!(ZoneMetadata::from_feature(
+            LocationZoneType::Forecast,
+            &unnamed
+        ));
+    }
+
     #[test]
     fn test_average_direction_single() {
         let directions = [Direction(90.0)];
@@ -782,4 +982,80 @@ mod tests {
         let directions: &[Direction] = &[];
         assert_eq!(average_direction(directions), None);
     }
+
+    #[test]
+    fn test_observation_tolerances_detect_change() {
+        let frame = |temperature: f32, timestamp: &str| -> WeatherFrame {
+            let reading = serde_json::json!({
+                "value": temperature,
+                "maxValue": temperature,
+                "minValue": temperature,
+                "unitCode": "wmoUnit:degC",
+                "qualityControl": "V",
+            });
+            serde_json::from_value(serde_json::json!({
+                "timestamp": timestamp,
+                "temperature": reading,
+            }))
+            .unwrap()
+        };
+
+        let prior = frame(12.0, "2023-03-20T12:00:00Z");
+        let tolerances = ObservationTolerances::new([(QuantitativeProperty::Temperature, 0.5)]);
+        assert!(tolerances.is_unchanged(&prior, &frame(12.4, "2023-03-20T12:15:00Z")));
+        assert!(!tolerances.is_unchanged(&prior, &frame(12.6, "2023-03-20T12:15:00Z")));
+
+        let mut missing = frame(12.0, "2023-03-20T12:15:00Z");
+        missing.temperature = None;
+        assert!(!tolerances.is_unchanged(&prior, &missing));
+
+        let exact = ObservationTolerances::default();
+        assert!(exact.is_unchanged(&prior, &frame(12.0, "2023-03-20T12:15:00Z")));
+        assert!(!exact.is_unchanged(&prior, &frame(12.1, "2023-03-20T12:15:00Z")));
+    }
+
+    #[test]
+    fn test_zone_observation_keeps_latest_reading_per_station() {
+        let feature = |station: &str, timestamp: &str, temperature: f32| {
+            serde_json::json!({
+                "type": "Feature",
+                "geometry": null,
+                "properties": {
+                    "station": format!("https://api.weather.gov/stations/{station}"),
+                    "timestamp": timestamp,
+                    "temperature": {
+                        "value": temperature,
+                        "unitCode": "wmoUnit:degC",
+                        "qualityControl": "V",
+                    },
+                },
+            })
+        };
+
+        let features: geojson::FeatureCollection = serde_json::from_value(serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [
+                feature("KSEA", "2023-03-20T12:00:00+00:00", 10.0),
+                feature("KSEA", "2023-03-20T12:15:00+00:00", 12.0),
+                feature("KBFI", "2023-03-20T12:10:00+00:00", 14.0),
+            ],
+        }))
+        .unwrap();
+
+        let observation = ZoneObservation::from(features);
+        assert_eq!(
+            observation.stations.keys().collect::<Vec<_>>(),
+            vec!["KBFI", "KSEA"]
+        );
+
+        let ksea = assert_some!(observation.stations["KSEA"].temperature.as_ref());
+        assert_relative_eq!(ksea.value, 12.0);
+        let kbfi = assert_some!(observation.stations["KBFI"].temperature.as_ref());
+        assert_relative_eq!(kbfi.value, 14.0);
+
+        let zone = assert_some!(observation.frame.temperature.as_ref());
+        assert_relative_eq!(zone.value, 12.0);
+        assert_relative_eq!(zone.max_value, 14.0);
+        assert_relative_eq!(zone.min_value, 10.0);
+    }
 }
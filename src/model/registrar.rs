@@ -1,30 +1,47 @@
+pub use bulk::{monitor_forecast_zones, RequestedZone, ZoneRegistration, ZoneRegistrationOutcome};
 pub use errors::RegistrarError;
-pub use protocol::{RegistrarCommand, RegistrarEvent};
+pub use protocol::{QueuedUpdate, RegistrarCommand, RegistrarEvent};
 pub use queries::{
-    MonitoredZonesQuery, MonitoredZonesView, MonitoredZonesViewProjection,
-    MONITORED_ZONES_QUERY_VIEW,
+    list_watchlists, load_monitored_zones, MonitoredZone, MonitoredZonesQuery,
+    MonitoredZonesUpdateQuery, MonitoredZonesView, MonitoredZonesViewProjection,
+    UpdateFinishedNotifier, UpdateInFlight, WeatherUpdateStarter, ZoneUpdateSummary,
+    ZoneWatchNotifier, MONITORED_ZONES_QUERY_VIEW,
 };
+pub use request::{request_weather_update, WeatherUpdateDisposition, WeatherUpdateRequest};
 pub use service::{FullRegistrarServices, HappyPathServices, RegistrarServices};
 
-use super::{registrar, LocationZoneAggregate, LocationZoneCode, UpdateLocationsSaga};
-use crate::model::TracingQuery;
+use super::{
+    registrar, LocationZone, LocationZoneAggregate, LocationZoneCode, LocationZoneType,
+    UpdateLocationsSaga,
+};
+use crate::model::snapshots::make_postgres_cqrs;
+use crate::model::update::{LocationUpdatedSteps, UpdateLocationsId};
+use crate::model::zone::LocationZoneError;
+use crate::model::{self, TracingQuery};
+use crate::settings::ConcurrentUpdatePolicy;
 use async_trait::async_trait;
-use cqrs_es::Aggregate;
+use chrono::Utc;
+use cqrs_es::{Aggregate, AggregateError};
 use postgres_es::{PostgresCqrs, PostgresViewRepository};
 use serde::{Deserialize, Serialize};
 use service::RegistrarApi;
 use sqlx::PgPool;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use smol_str::SmolStr;
-use tagid::{Entity, Id, Label};
+use tagid::Label;
+use tokio::sync::mpsc;
+use utoipa::{IntoParams, ToSchema};
 
 pub type RegistrarAggregate = Arc<PostgresCqrs<Registrar>>;
 
 pub const AGGREGATE_TYPE: &str = "registrar";
 
 pub fn make_registrar_aggregate(
-    db_pool: PgPool, location_agg: LocationZoneAggregate, update_saga: UpdateLocationsSaga,
+    db_pool: PgPool, location_agg: LocationZoneAggregate,
+    location_tx: mpsc::Sender<model::CommandEnvelope<LocationZone>>,
+    registrar_tx: mpsc::Sender<model::CommandEnvelope<Registrar>>,
+    update_saga: UpdateLocationsSaga, update_policy: ConcurrentUpdatePolicy,
+    snapshot_interval: Option<usize>,
 ) -> (RegistrarAggregate, MonitoredZonesViewProjection) {
     let monitored_zones_view = Arc::new(PostgresViewRepository::new(
         MONITORED_ZONES_QUERY_VIEW,
@@ -35,45 +52,310 @@ pub fn make_registrar_aggregate(
         tracing::error!(?error, "monitored zones query failed")
     }));
 
-    let agg = Arc::new(postgres_es::postgres_cqrs(
-        db_pool,
+    let agg = Arc::new(make_postgres_cqrs(
+        db_pool.clone(),
         vec![
             Box::<TracingQuery<Registrar>>::default(),
             Box::new(monitored_zones_query),
+            Box::new(ZoneWatchNotifier::new(location_tx)),
+            Box::new(WeatherUpdateStarter::new(update_saga, registrar_tx)),
         ],
         // vec![Box::new(TracingQuery::<Registrar>::default())],
         RegistrarServices::Full(registrar::FullRegistrarServices::new(
             location_agg,
-            update_saga,
+            db_pool,
+            update_policy,
         )),
+        snapshot_interval,
     ));
 
     (agg, monitored_zones_view)
 }
 
-pub type RegistrarId = Id<Registrar, <<Registrar as Entity>::IdGen as tagid::IdGenerator>::IdType>;
+/// Metadata key recording the watchlist on which a command or event was requested.
+pub const WATCHLIST_METADATA: &str = "watchlist";
+
+/// Metadata key recording who requested a registrar command; e.g., from the `X-Requested-By`
+/// header.
+pub const REQUESTED_BY_METADATA: &str = "requested_by";
+
+/// Metadata key recording when a registrar command was requested, as an RFC 3339 timestamp.
+pub const REQUESTED_AT_METADATA: &str = "requested_at";
+
+/// Builds the metadata recording who requested a registrar command, and when.
+pub fn request_metadata(requested_by: &str) -> HashMap<String, String> {
+    model::with_occurred_at(maplit::hashmap! {
+        REQUESTED_BY_METADATA.to_string() => requested_by.to_string(),
+        REQUESTED_AT_METADATA.to_string() => Utc::now().to_rfc3339(),
+    })
+}
+
+/// Watchlist serving the original, single-registrar routes. Data recorded under the former
+/// registrar singleton is migrated onto this watchlist.
+pub const DEFAULT_WATCHLIST_ID: &str = "default";
+
+/// Longest watchlist identifier accepted.
+pub const MAX_WATCHLIST_ID_LEN: usize = 64;
+
+/// Identifies a named watchlist, which is the aggregate id of its `Registrar`. Identifiers
+/// received from requests are 1 to 64 ASCII letters, digits, `-`, `_` or `.`.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, IntoParams, ToSchema, Serialize, Deserialize,
+)]
+#[into_params(names("watchlist_id"))]
+#[repr(transparent)]
+#[serde(try_from = "String", into = "String")]
+pub struct WatchlistId(String);
+
+impl WatchlistId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Parses the watchlist identifier, rejecting identifiers of an invalid format.
+    pub fn parse(id: impl Into<String>) -> Result<Self, RegistrarError> {
+        let id = id.into();
+        let is_valid = !id.is_empty()
+            && id.len() <= MAX_WATCHLIST_ID_LEN
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+        if is_valid {
+            Ok(Self(id))
+        } else {
+            Err(RegistrarError::InvalidWatchlist(id))
+        }
+    }
+}
+
+impl TryFrom<String> for WatchlistId {
+    type Error = RegistrarError;
 
-#[inline]
-pub fn singleton_id() -> RegistrarId {
-    Registrar::next_id()
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        Self::parse(id)
+    }
+}
+
+impl Default for WatchlistId {
+    fn default() -> Self {
+        Self::new(DEFAULT_WATCHLIST_ID)
+    }
+}
+
+impl std::fmt::Display for WatchlistId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for WatchlistId {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<WatchlistId> for String {
+    fn from(id: WatchlistId) -> Self {
+        id.0
+    }
+}
+
+/// Free-form zone label; e.g., `region:mid-atlantic` or `customer:acme`.
+pub type ZoneLabel = String;
+
+/// Selects the zones carrying every label in the selector. An empty selector selects all zones.
+#[derive(Debug, Default, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[repr(transparent)]
+#[serde(transparent)]
+pub struct LabelSelector(BTreeSet<ZoneLabel>);
+
+impl LabelSelector {
+    pub fn new(labels: impl IntoIterator<Item = impl Into<ZoneLabel>>) -> Self {
+        Self(labels.into_iter().map(|label| label.into()).collect())
+    }
+
+    /// Parses a comma-separated list of labels.
+    pub fn parse(selector: &str) -> Self {
+        Self::new(selector.split(',').map(str::trim).filter(|label| !label.is_empty()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn matches(&self, labels: Option<&BTreeSet<ZoneLabel>>) -> bool {
+        self.0
+            .iter()
+            .all(|label| labels.map_or(false, |zone_labels| zone_labels.contains(label)))
+    }
+}
+
+impl std::fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let labels: Vec<_> = self.0.iter().map(String::as_str).collect();
+        write!(f, "{}", labels.join(","))
+    }
+}
+
+/// Zones and steps covered by a weather update.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateScope {
+    /// Selects the monitored zones to update by label.
+    #[serde(default)]
+    pub selector: LabelSelector,
+
+    /// Restricts the update to these monitored zones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zones: Option<Vec<LocationZoneCode>>,
+
+    #[serde(default = "LocationUpdatedSteps::all")]
+    pub steps: LocationUpdatedSteps,
+}
+
+impl Default for UpdateScope {
+    fn default() -> Self {
+        Self {
+            selector: LabelSelector::default(),
+            zones: None,
+            steps: LocationUpdatedSteps::all(),
+        }
+    }
+}
+
+impl UpdateScope {
+    fn includes(&self, zone: &LocationZoneCode) -> bool {
+        self.zones.as_ref().map_or(true, |zones| zones.contains(zone))
+    }
 }
 
 #[derive(Debug, Default, Clone, Label, PartialEq, Serialize, Deserialize)]
 pub struct Registrar {
-    location_codes: HashSet<LocationZoneCode>,
-}
+    location_codes: HashMap<LocationZoneCode, LocationZoneType>,
 
-pub struct SingletonIdGenerator;
+    #[serde(default)]
+    labels: HashMap<LocationZoneCode, BTreeSet<ZoneLabel>>,
 
-const REGISTRAR_SINGLETON_ID: &str = "<singleton>";
+    /// Update sagas started by the registrar and not yet finished.
+    #[serde(default)]
+    updates_in_flight: HashMap<String, ZoneUpdate>,
 
-impl tagid::IdGenerator for SingletonIdGenerator {
-    type IdType = SmolStr;
-    fn next_id_rep() -> Self::IdType { SmolStr::new(REGISTRAR_SINGLETON_ID) }
+    /// Update requests held until the overlapping updates in flight finish.
+    #[serde(default)]
+    queued_updates: VecDeque<QueuedUpdate>,
 }
 
-impl Entity for Registrar {
-    type IdGen = SingletonIdGenerator;
+/// The zones and steps an update saga is updating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ZoneUpdate {
+    zones: Vec<LocationZoneCode>,
+    steps: LocationUpdatedSteps,
+}
+
+impl ZoneUpdate {
+    fn overlaps(&self, other: &Self) -> bool {
+        self.steps.intersects(other.steps) && self.zones.iter().any(|z| other.zones.contains(z))
+    }
+}
+
+impl Registrar {
+    fn select_update(&self, scope: &UpdateScope) -> ZoneUpdate {
+        let zones = self
+            .location_codes
+            .keys()
+            .filter(|zone| scope.includes(zone) && scope.selector.matches(self.labels.get(*zone)))
+            .cloned()
+            .collect();
+
+        ZoneUpdate { zones, steps: scope.steps }
+    }
+
+    /// Finds an update in flight for any of the steps of the zones.
+    fn find_overlapping_update(&self, update: &ZoneUpdate) -> Option<&str> {
+        self.updates_in_flight
+            .iter()
+            .find(|(_, in_flight)| in_flight.overlaps(update))
+            .map(|(saga_id, _)| saga_id.as_str())
+    }
+
+    /// Starts the update over its zones that are not suspended. The update saga itself is
+    /// started by the `WeatherUpdateStarter` once the `WeatherUpdateStarted` event is committed.
+    /// An update without any zone or step to update is rejected rather than started.
+    async fn start_update(
+        &self, saga_id: &UpdateLocationsId, update: ZoneUpdate, service: &RegistrarServices,
+    ) -> Result<Vec<RegistrarEvent>, RegistrarError> {
+        if update.zones.is_empty() || update.steps.is_empty() {
+            return Err(RegistrarError::NothingToUpdate(
+                "no monitored zone or update step in scope".to_string(),
+            ));
+        }
+
+        let zone_refs: Vec<_> = update.zones.iter().collect();
+        let suspended = service.find_suspended_zones(&zone_refs).await?;
+        if !suspended.is_empty() {
+            tracing::info!(?suspended, "skipping update of suspended zones");
+        }
+
+        let zones: Vec<_> =
+            update.zones.into_iter().filter(|zone| !suspended.contains(zone)).collect();
+        if zones.is_empty() {
+            return Err(RegistrarError::NothingToUpdate(
+                "every zone in scope is suspended".to_string(),
+            ));
+        }
+
+        Ok(vec![RegistrarEvent::WeatherUpdateStarted {
+            saga_id: saga_id.id.to_string(),
+            zones,
+            steps: update.steps,
+        }])
+    }
+
+    /// Once an update finishes, starts the queued updates that no longer overlap an update in
+    /// flight. A queued update that no longer selects any zone, or fails to start, is finished
+    /// in place.
+    async fn finish_update(
+        &self, saga_id: String, service: &RegistrarServices,
+    ) -> Vec<RegistrarEvent> {
+        let mut finished = vec![saga_id.clone()];
+        let mut events = vec![RegistrarEvent::WeatherUpdateFinished(saga_id)];
+
+        let mut started: Vec<ZoneUpdate> = vec![];
+        for queued in self.queued_updates.iter() {
+            let update = self.select_update(&queued.scope);
+            let overlaps_in_flight = self
+                .updates_in_flight
+                .iter()
+                .filter(|(id, _)| !finished.contains(*id))
+                .map(|(_, in_flight)| in_flight)
+                .chain(started.iter())
+                .any(|in_flight| in_flight.overlaps(&update));
+            if overlaps_in_flight {
+                continue;
+            }
+
+            let queued_id = queued.saga_id.id.to_string();
+            let outcome = self.start_update(&queued.saga_id, update.clone(), service).await;
+            match outcome {
+                Ok(start_events) => {
+                    started.push(update);
+                    events.extend(start_events);
+                },
+                Err(RegistrarError::NothingToUpdate(reason)) => {
+                    tracing::info!(%queued_id, %reason, "queued weather update has nothing to update");
+                    finished.push(queued_id.clone());
+                    events.push(RegistrarEvent::WeatherUpdateFinished(queued_id));
+                },
+                Err(error) => {
+                    tracing::error!(?error, %queued_id, "failed to start queued weather update");
+                    finished.push(queued_id.clone());
+                    events.push(RegistrarEvent::WeatherUpdateFinished(queued_id));
+                },
+            }
+        }
+
+        events
+    }
 }
 
 #[async_trait]
@@ -92,57 +374,446 @@ impl Aggregate for Registrar {
         &self, command: Self::Command, service: &Self::Services,
     ) -> Result<Vec<Self::Event>, Self::Error> {
         match command {
-            RegistrarCommand::UpdateWeather => {
-                let loc_codes: Vec<_> = self.location_codes.iter().collect();
-                service.update_weather(&loc_codes).await.map(|_| vec![])
+            RegistrarCommand::UpdateWeather { saga_id, watchlist, scope } => {
+                let unmonitored: Vec<_> = scope
+                    .zones
+                    .iter()
+                    .flatten()
+                    .filter(|zone| !self.location_codes.contains_key(*zone))
+                    .map(|zone| zone.to_string())
+                    .collect();
+                if !unmonitored.is_empty() {
+                    return Err(RegistrarError::RejectedCommand(format!(
+                        "cannot update unmonitored location zone codes: {}",
+                        unmonitored.join(",")
+                    )));
+                }
+
+                let update = self.select_update(&scope);
+                let running = match self.find_overlapping_update(&update) {
+                    Some(running) => running.to_string(),
+                    None => return self.start_update(&saga_id, update, service).await,
+                };
+
+                match service.concurrent_update_policy() {
+                    ConcurrentUpdatePolicy::Reject => {
+                        Err(RegistrarError::UpdateInProgress(running))
+                    },
+                    ConcurrentUpdatePolicy::Coalesce => {
+                        Ok(vec![RegistrarEvent::WeatherUpdateCoalesced {
+                            saga_id: saga_id.id.to_string(),
+                            into: running,
+                        }])
+                    },
+                    ConcurrentUpdatePolicy::Queue => {
+                        let queued = QueuedUpdate { saga_id, watchlist, scope };
+                        Ok(vec![RegistrarEvent::WeatherUpdateQueued(queued)])
+                    },
+                }
+            },
+            RegistrarCommand::NoteWeatherUpdateFinished(saga_id) => {
+                if self.updates_in_flight.contains_key(&saga_id) {
+                    Ok(self.finish_update(saga_id, service).await)
+                } else {
+                    Ok(vec![])
+                }
             },
-            RegistrarCommand::MonitorForecastZone(zone) if !self.location_codes.contains(&zone) => {
-                service.initialize_forecast_zone(&zone).await?;
-                Ok(vec![RegistrarEvent::ForecastZoneAdded(zone)])
+            RegistrarCommand::MonitorForecastZone(zone, zone_type)
+                if !self.location_codes.contains_key(&zone) =>
+            {
+                service.initialize_forecast_zone(&zone, zone_type).await?;
+                Ok(vec![RegistrarEvent::ZoneAdded(zone, zone_type)])
             },
-            RegistrarCommand::MonitorForecastZone(zone) => Err(RegistrarError::RejectedCommand(
+            RegistrarCommand::MonitorForecastZone(zone, _) => Err(RegistrarError::RejectedCommand(
                 format!("already monitoring location zone code: {zone}"),
             )),
+            RegistrarCommand::MonitorForecastZones(zones) => {
+                let mut added = HashSet::with_capacity(zones.len());
+                let mut events = Vec::with_capacity(zones.len());
+                for (zone, zone_type) in zones {
+                    if self.location_codes.contains_key(&zone) || added.contains(&zone) {
+                        continue;
+                    }
+
+                    match service.initialize_forecast_zone(&zone, zone_type).await {
+                        Ok(()) => {
+                            added.insert(zone.clone());
+                            events.push(RegistrarEvent::ZoneAdded(zone, zone_type));
+                        },
+                        Err(error) => {
+                            tracing::warn!(
+                                ?error, %zone,
+                                "failed to initialize forecast zone - skipping"
+                            );
+                            let unknown_zone = matches!(
+                                &error,
+                                RegistrarError::LocationZone(AggregateError::UserError(
+                                    LocationZoneError::UnknownZone(_)
+                                ))
+                            );
+                            events.push(RegistrarEvent::ZoneRegistrationFailed {
+                                zone,
+                                unknown_zone,
+                                reason: error.to_string(),
+                            });
+                        },
+                    }
+                }
+                Ok(events)
+            },
             RegistrarCommand::ClearZoneMonitoring => {
-                Ok(vec![RegistrarEvent::AllForecastZonesForgotten])
+                let mut zones: Vec<_> = self.location_codes.keys().cloned().collect();
+                zones.sort_by(|lhs, rhs| lhs.as_ref().cmp(rhs.as_ref()));
+                Ok(zones.into_iter().map(RegistrarEvent::ForecastZoneForgotten).collect())
             },
             RegistrarCommand::ForgetForecastZone(zone) => {
                 Ok(vec![RegistrarEvent::ForecastZoneForgotten(zone)])
             },
+            RegistrarCommand::LabelZone(zone, _) if !self.location_codes.contains_key(&zone) => {
+                Err(RegistrarError::RejectedCommand(format!(
+                    "cannot label unmonitored location zone code: {zone}"
+                )))
+            },
+            RegistrarCommand::LabelZone(zone, labels) => {
+                if let Some(invalid) = labels.iter().find(|label| !is_valid_label(label)) {
+                    return Err(RegistrarError::RejectedCommand(format!(
+                        "invalid zone label: \"{invalid}\" - labels must be non-empty without \
+                         commas or surrounding whitespace"
+                    )));
+                }
+
+                let current = self.labels.get(&zone);
+                let added: BTreeSet<_> = labels
+                    .into_iter()
+                    .filter(|label| !current.map_or(false, |c| c.contains(label)))
+                    .collect();
+
+                if added.is_empty() {
+                    Ok(vec![])
+                } else {
+                    Ok(vec![RegistrarEvent::ZoneLabelsAdded(zone, added)])
+                }
+            },
+            RegistrarCommand::UnlabelZone(zone, labels) => {
+                let current = self.labels.get(&zone);
+                let removed: BTreeSet<_> = labels
+                    .into_iter()
+                    .filter(|label| current.map_or(false, |c| c.contains(label)))
+                    .collect();
+
+                if removed.is_empty() {
+                    Ok(vec![])
+                } else {
+                    Ok(vec![RegistrarEvent::ZoneLabelsRemoved(zone, removed)])
+                }
+            },
         }
     }
 
     #[tracing::instrument(level = "debug")]
     fn apply(&mut self, event: Self::Event) {
         match event {
-            RegistrarEvent::ForecastZoneAdded(zone) => {
-                self.location_codes.insert(zone);
+            RegistrarEvent::ZoneAdded(zone, zone_type) => {
+                self.location_codes.insert(zone, zone_type);
             },
             RegistrarEvent::ForecastZoneForgotten(zone) => {
                 self.location_codes.remove(&zone);
+                self.labels.remove(&zone);
             },
             RegistrarEvent::AllForecastZonesForgotten => {
                 self.location_codes.clear();
+                self.labels.clear();
+            },
+            RegistrarEvent::ZoneLabelsAdded(zone, labels) => {
+                self.labels.entry(zone).or_default().extend(labels);
+            },
+            RegistrarEvent::ZoneLabelsRemoved(zone, labels) => {
+                if let Some(zone_labels) = self.labels.get_mut(&zone) {
+                    zone_labels.retain(|label| !labels.contains(label));
+                    if zone_labels.is_empty() {
+                        self.labels.remove(&zone);
+                    }
+                }
+            },
+            RegistrarEvent::WeatherUpdateStarted { saga_id, zones, steps } => {
+                self.queued_updates
+                    .retain(|queued| queued.saga_id.id.to_string() != saga_id);
+                self.updates_in_flight.insert(saga_id, ZoneUpdate { zones, steps });
+            },
+            RegistrarEvent::ZoneRegistrationFailed { .. } => {},
+            RegistrarEvent::WeatherUpdateCoalesced { .. } => {},
+            RegistrarEvent::WeatherUpdateQueued(queued) => {
+                self.queued_updates.push_back(queued);
+            },
+            RegistrarEvent::WeatherUpdateFinished(saga_id) => {
+                self.updates_in_flight.remove(&saga_id);
+                self.queued_updates
+                    .retain(|queued| queued.saga_id.id.to_string() != saga_id);
             },
         }
     }
 }
 
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty() && label.trim() == label && !label.contains(',')
+}
+
+mod bulk {
+    use super::{RegistrarCommand, RegistrarError, RegistrarEvent, WatchlistId, AGGREGATE_TYPE};
+    use crate::model::{self, LocationZoneCode, LocationZoneType, RegistrarAggregate};
+    use cqrs_es::persist::PersistenceError;
+    use cqrs_es::AggregateError;
+    use serde::{Deserialize, Serialize};
+    use sql_query_builder as sql;
+    use sqlx::types::Json;
+    use sqlx::PgPool;
+    use std::collections::{HashMap, HashSet};
+    use strum_macros::Display;
+    use utoipa::ToSchema;
+
+    /// Zone code requested for registration, with its zone type if other than implied by the
+    /// code.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RequestedZone {
+        pub code: String,
+        pub zone_type: Option<LocationZoneType>,
+    }
+
+    impl RequestedZone {
+        pub fn new(code: impl Into<String>, zone_type: Option<LocationZoneType>) -> Self {
+            Self { code: code.into(), zone_type }
+        }
+
+        /// The requested zone, typed by the default unless given its own type.
+        pub fn or_zone_type(self, default_type: Option<LocationZoneType>) -> Self {
+            Self { zone_type: self.zone_type.or(default_type), ..self }
+        }
+    }
+
+    #[derive(Debug, Display, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+    #[strum(serialize_all = "snake_case")]
+    #[serde(rename_all = "snake_case")]
+    pub enum ZoneRegistrationOutcome {
+        Added,
+        AlreadyMonitored,
+
+        /// The code repeats an earlier code of the request, whose outcome is reported there.
+        Duplicate,
+        Invalid,
+        Failed,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ZoneRegistration {
+        pub zone: String,
+        pub outcome: ZoneRegistrationOutcome,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+    }
+
+    impl ZoneRegistration {
+        fn new(zone: impl Into<String>, outcome: ZoneRegistrationOutcome) -> Self {
+            Self { zone: zone.into(), outcome, reason: None }
+        }
+
+        fn with_reason(self, reason: impl Into<String>) -> Self {
+            Self { reason: Some(reason.into()), ..self }
+        }
+    }
+
+    /// Registers the zone codes with the watchlist in one `MonitorForecastZones` command, and
+    /// reports the outcome for each requested code, in request order. Each zone's outcome is
+    /// taken from the events the command committed, which are correlated to this registration.
+    /// The command failing as a whole, e.g. on a conflicting registrar update, is returned as an
+    /// error rather than reported against each zone.
+    #[tracing::instrument(level = "debug", skip(registrar, db_pool))]
+    pub async fn monitor_forecast_zones(
+        watchlist: &WatchlistId, zones: Vec<RequestedZone>, metadata: HashMap<String, String>,
+        registrar: &RegistrarAggregate, db_pool: &PgPool,
+    ) -> Result<Vec<ZoneRegistration>, AggregateError<RegistrarError>> {
+        let mut candidates: Vec<(LocationZoneCode, LocationZoneType)> = Vec::new();
+        for requested in zones.iter() {
+            let zone = LocationZoneCode::new(requested.code.trim());
+            if zone.is_well_formed() && !candidates.iter().any(|(c, _)| c == &zone) {
+                let zone_type = requested.zone_type.unwrap_or_else(|| zone.implied_zone_type());
+                candidates.push((zone, zone_type));
+            }
+        }
+
+        let zone_codes: Vec<_> = zones.into_iter().map(|requested| requested.code).collect();
+
+        if candidates.is_empty() {
+            return Ok(registration_report(zone_codes, &[]));
+        }
+
+        let correlation = format!("zone-registration-{:016x}", rand::random::<u64>());
+        let mut metadata = metadata;
+        metadata.insert(model::CORRELATION_METADATA.to_string(), correlation.clone());
+
+        let command = RegistrarCommand::MonitorForecastZones(candidates);
+        registrar
+            .execute_with_metadata(watchlist.as_ref(), command, metadata)
+            .await?;
+
+        let events = load_correlated_events(watchlist, &correlation, db_pool).await?;
+        Ok(registration_report(zone_codes, &events))
+    }
+
+    /// Reports the outcome of each requested zone code from the registration's events. A
+    /// well-formed zone neither added nor failed was already monitored. A code repeating an
+    /// earlier code of the request is reported a duplicate.
+    pub(super) fn registration_report(
+        zone_codes: Vec<String>, events: &[RegistrarEvent],
+    ) -> Vec<ZoneRegistration> {
+        let mut reported = HashSet::new();
+        zone_codes
+            .into_iter()
+            .map(|code| {
+                let zone = LocationZoneCode::new(code.trim());
+                if !zone.is_well_formed() {
+                    return ZoneRegistration::new(code, ZoneRegistrationOutcome::Invalid)
+                        .with_reason("not a NWS zone code; e.g., WAZ558 or WAC033");
+                }
+
+                if !reported.insert(zone.clone()) {
+                    return ZoneRegistration::new(zone, ZoneRegistrationOutcome::Duplicate)
+                        .with_reason("repeats an earlier zone code of the request");
+                }
+
+                let outcome = events.iter().find_map(|event| match event {
+                    RegistrarEvent::ZoneAdded(added, _) if added == &zone => Some(
+                        ZoneRegistration::new(zone.clone(), ZoneRegistrationOutcome::Added),
+                    ),
+                    RegistrarEvent::ZoneRegistrationFailed {
+                        zone: failed,
+                        unknown_zone,
+                        reason,
+                    } if failed == &zone => {
+                        let outcome = if *unknown_zone {
+                            ZoneRegistrationOutcome::Invalid
+                        } else {
+                            ZoneRegistrationOutcome::Failed
+                        };
+                        Some(ZoneRegistration::new(zone.clone(), outcome).with_reason(reason))
+                    },
+                    _ => None,
+                });
+
+                outcome.unwrap_or_else(|| {
+                    ZoneRegistration::new(zone, ZoneRegistrationOutcome::AlreadyMonitored)
+                })
+            })
+            .collect()
+    }
+
+    async fn load_correlated_events(
+        watchlist: &WatchlistId, correlation: &str, db_pool: &PgPool,
+    ) -> Result<Vec<RegistrarEvent>, PersistenceError> {
+        let select_sql = sql::Select::new()
+            .select("payload")
+            .from("events")
+            .where_clause("aggregate_type = $1")
+            .where_clause("aggregate_id = $2")
+            .where_clause("metadata ->> $3 = $4")
+            .order_by("sequence")
+            .to_string();
+
+        let rows: Vec<(Json<RegistrarEvent>,)> = sqlx::query_as(&select_sql)
+            .bind(AGGREGATE_TYPE)
+            .bind(watchlist.as_ref())
+            .bind(model::CORRELATION_METADATA)
+            .bind(correlation)
+            .fetch_all(db_pool)
+            .await
+            .map_err(|err| PersistenceError::ConnectionError(Box::new(err)))?;
+
+        Ok(rows.into_iter().map(|(Json(event),)| event).collect())
+    }
+}
+
+mod request {
+    use super::{
+        MonitoredZonesViewProjection, RegistrarCommand, RegistrarError, UpdateScope, WatchlistId,
+    };
+    use crate::model::{update, RegistrarAggregate};
+    use cqrs_es::persist::ViewRepository;
+    use cqrs_es::AggregateError;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+    use strum_macros::Display;
+    use utoipa::ToSchema;
+
+    /// How the registrar handled a weather update request, per its concurrent update policy.
+    #[derive(Debug, Display, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+    #[strum(serialize_all = "snake_case")]
+    #[serde(rename_all = "snake_case")]
+    pub enum WeatherUpdateDisposition {
+        Started,
+        Coalesced,
+        Queued,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct WeatherUpdateRequest {
+        /// Identifies the update saga serving the request; i.e., the running update for a
+        /// coalesced request.
+        pub update_id: String,
+        pub disposition: WeatherUpdateDisposition,
+    }
+
+    /// Requests a weather update of the watchlist zones and steps in scope, and reports which
+    /// update serves the request.
+    #[tracing::instrument(level = "debug", skip(registrar, monitored_zones_view))]
+    pub async fn request_weather_update(
+        watchlist: &WatchlistId, scope: UpdateScope, metadata: HashMap<String, String>,
+        registrar: &RegistrarAggregate, monitored_zones_view: &MonitoredZonesViewProjection,
+    ) -> Result<WeatherUpdateRequest, AggregateError<RegistrarError>> {
+        let saga_id = update::generate_id();
+        let command = RegistrarCommand::UpdateWeather {
+            saga_id: saga_id.clone(),
+            watchlist: watchlist.clone(),
+            scope,
+        };
+        registrar
+            .execute_with_metadata(watchlist.as_ref(), command, metadata)
+            .await?;
+
+        let requested = saga_id.id.to_string();
+        let (update_id, disposition) = monitored_zones_view
+            .load(watchlist.as_ref())
+            .await?
+            .map(|view| view.disposition_of(&requested))
+            .unwrap_or((requested, WeatherUpdateDisposition::Started));
+
+        Ok(WeatherUpdateRequest { update_id, disposition })
+    }
+}
+
 mod service {
     use super::RegistrarError;
-    use crate::model::update::UpdateLocationsCommand;
-    use crate::model::zone::LocationZoneCommand;
-    use crate::model::{LocationZoneAggregate, LocationZoneCode, UpdateLocationsSaga};
+    use crate::model::zone::{self, LocationZoneCommand};
+    use crate::model::{self, LocationZoneAggregate, LocationZoneCode, LocationZoneType};
+    use crate::settings::ConcurrentUpdatePolicy;
     use async_trait::async_trait;
+    use sqlx::PgPool;
+    use std::collections::HashSet;
     use std::fmt;
 
     #[async_trait]
     pub trait RegistrarApi: Sync + Send {
         async fn initialize_forecast_zone(
-            &self, zone: &LocationZoneCode,
+            &self, zone: &LocationZoneCode, zone_type: LocationZoneType,
         ) -> Result<(), RegistrarError>;
 
-        async fn update_weather(&self, zones: &[&LocationZoneCode]) -> Result<(), RegistrarError>;
+        /// Finds which of the zones have their updates suspended.
+        async fn find_suspended_zones(
+            &self, zones: &[&LocationZoneCode],
+        ) -> Result<HashSet<LocationZoneCode>, RegistrarError>;
+
+        fn concurrent_update_policy(&self) -> ConcurrentUpdatePolicy;
     }
 
     #[derive(Debug, Clone)]
@@ -154,18 +825,27 @@ mod service {
     #[async_trait]
     impl RegistrarApi for RegistrarServices {
         async fn initialize_forecast_zone(
-            &self, zone: &LocationZoneCode,
+            &self, zone: &LocationZoneCode, zone_type: LocationZoneType,
         ) -> Result<(), RegistrarError> {
             match self {
-                Self::Full(svc) => svc.initialize_forecast_zone(zone).await,
-                Self::HappyPath(svc) => svc.initialize_forecast_zone(zone).await,
+                Self::Full(svc) => svc.initialize_forecast_zone(zone, zone_type).await,
+                Self::HappyPath(svc) => svc.initialize_forecast_zone(zone, zone_type).await,
+            }
+        }
+
+        async fn find_suspended_zones(
+            &self, zones: &[&LocationZoneCode],
+        ) -> Result<HashSet<LocationZoneCode>, RegistrarError> {
+            match self {
+                Self::Full(svc) => svc.find_suspended_zones(zones).await,
+                Self::HappyPath(svc) => svc.find_suspended_zones(zones).await,
             }
         }
 
-        async fn update_weather(&self, zones: &[&LocationZoneCode]) -> Result<(), RegistrarError> {
+        fn concurrent_update_policy(&self) -> ConcurrentUpdatePolicy {
             match self {
-                Self::Full(svc) => svc.update_weather(zones).await,
-                Self::HappyPath(svc) => svc.update_weather(zones).await,
+                Self::Full(svc) => svc.concurrent_update_policy(),
+                Self::HappyPath(svc) => svc.concurrent_update_policy(),
             }
         }
     }
@@ -173,12 +853,15 @@ mod service {
     #[derive(Clone)]
     pub struct FullRegistrarServices {
         location: LocationZoneAggregate,
-        update: UpdateLocationsSaga,
+        db_pool: PgPool,
+        update_policy: ConcurrentUpdatePolicy,
     }
 
     impl FullRegistrarServices {
-        pub fn new(location: LocationZoneAggregate, update: UpdateLocationsSaga) -> Self {
-            Self { location, update }
+        pub fn new(
+            location: LocationZoneAggregate, db_pool: PgPool, update_policy: ConcurrentUpdatePolicy,
+        ) -> Self {
+            Self { location, db_pool, update_policy }
         }
     }
 
@@ -191,30 +874,27 @@ mod service {
     #[async_trait]
     impl RegistrarApi for FullRegistrarServices {
         async fn initialize_forecast_zone(
-            &self, zone: &LocationZoneCode,
+            &self, zone: &LocationZoneCode, zone_type: LocationZoneType,
         ) -> Result<(), RegistrarError> {
             let aggregate_id = zone.as_ref();
-            let command = LocationZoneCommand::WatchZone(zone.clone());
-            self.location.execute(aggregate_id, command).await?;
+            let command = LocationZoneCommand::WatchZone(zone.clone(), zone_type);
+            let metadata = model::occurrence_metadata();
+            self.location
+                .execute_with_metadata(aggregate_id, command, metadata)
+                .await?;
             Ok(())
         }
 
         #[tracing::instrument(level = "debug", skip(self))]
-        async fn update_weather(&self, zones: &[&LocationZoneCode]) -> Result<(), RegistrarError> {
-            if zones.is_empty() {
-                return Ok(());
-            }
-
-            let zone_ids = zones.iter().copied().cloned().collect();
+        async fn find_suspended_zones(
+            &self, zones: &[&LocationZoneCode],
+        ) -> Result<HashSet<LocationZoneCode>, RegistrarError> {
+            let suspended = zone::find_suspended_zones(&self.db_pool, zones).await?;
+            Ok(suspended)
+        }
 
-            let saga_id = crate::model::update::generate_id();
-            tracing::debug!("DMR: Update Locations identifier: {saga_id:?}");
-            let metadata =
-                maplit::hashmap! { "correlation".to_string() => saga_id.id.to_string(), };
-            let command = UpdateLocationsCommand::UpdateLocations(saga_id.clone(), zone_ids);
-            self.update.execute_with_metadata(&saga_id.id, command, metadata).await?;
-            // Ok(events)
-            Ok(())
+        fn concurrent_update_policy(&self) -> ConcurrentUpdatePolicy {
+            self.update_policy
         }
     }
 
@@ -224,34 +904,65 @@ mod service {
     #[async_trait]
     impl RegistrarApi for HappyPathServices {
         async fn initialize_forecast_zone(
-            &self, _zone: &LocationZoneCode,
+            &self, _zone: &LocationZoneCode, _zone_type: LocationZoneType,
         ) -> Result<(), RegistrarError> {
             Ok(())
         }
 
-        async fn update_weather(&self, _zones: &[&LocationZoneCode]) -> Result<(), RegistrarError> {
-            // let events = zones
-            //     .iter()
-            //     .map(|(loc, zone)| RegistrarEvent::LocationAdded(**loc, (*zone).clone()))
-            //     .collect();
-            // Ok(events)
-            Ok(())
+        async fn find_suspended_zones(
+            &self, _zones: &[&LocationZoneCode],
+        ) -> Result<HashSet<LocationZoneCode>, RegistrarError> {
+            Ok(HashSet::new())
+        }
+
+        fn concurrent_update_policy(&self) -> ConcurrentUpdatePolicy {
+            ConcurrentUpdatePolicy::default()
         }
     }
 }
 
 mod protocol {
-    use crate::model::LocationZoneCode;
+    use super::{UpdateScope, WatchlistId, ZoneLabel};
+    use crate::model::update::{LocationUpdatedSteps, UpdateLocationsId};
+    use crate::model::{EventUpcasterRegistry, LocationZoneCode, LocationZoneType, VersionedEvent};
     use cqrs_es::DomainEvent;
     use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::collections::BTreeSet;
     use strum_macros::Display;
 
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
     pub enum RegistrarCommand {
-        UpdateWeather,
-        MonitorForecastZone(LocationZoneCode),
+        /// Update the weather of the monitored zones and steps in scope. The watchlist repeats the
+        /// registrar's own aggregate id, which the registrar does not otherwise know, so the
+        /// update saga can be traced back to its watchlist.
+        UpdateWeather {
+            saga_id: UpdateLocationsId,
+            watchlist: WatchlistId,
+            scope: UpdateScope,
+        },
+        MonitorForecastZone(LocationZoneCode, LocationZoneType),
+        /// Monitor each zone not already monitored, as its zone type, in a single registrar
+        /// transaction. Zones that fail to initialize are recorded as failed registrations rather
+        /// than failing the command.
+        MonitorForecastZones(Vec<(LocationZoneCode, LocationZoneType)>),
         ClearZoneMonitoring,
         ForgetForecastZone(LocationZoneCode),
+        LabelZone(LocationZoneCode, BTreeSet<ZoneLabel>),
+        UnlabelZone(LocationZoneCode, BTreeSet<ZoneLabel>),
+        /// Notes an update saga started by the registrar has finished, which may release queued
+        /// updates.
+        NoteWeatherUpdateFinished(String),
+    }
+
+    /// Weather update request held until the overlapping updates in flight finish.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct QueuedUpdate {
+        pub saga_id: UpdateLocationsId,
+        pub watchlist: WatchlistId,
+
+        #[serde(flatten)]
+        pub scope: UpdateScope,
     }
 
     const VERSION: &str = "1.0";
@@ -259,9 +970,33 @@ mod protocol {
     #[derive(Debug, Display, Clone, PartialEq, Eq, Serialize, Deserialize)]
     #[strum(serialize_all = "snake_case")]
     pub enum RegistrarEvent {
-        ForecastZoneAdded(LocationZoneCode),
+        /// Zone added to monitoring. Zones added before zone types were tracked, stored as
+        /// `forecast_zone_added` events, are upcast with the type implied by the zone code.
+        ZoneAdded(LocationZoneCode, LocationZoneType),
         ForecastZoneForgotten(LocationZoneCode),
+        /// Every zone forgotten at once, as recorded before clearing forgot each zone in turn.
         AllForecastZonesForgotten,
+        ZoneLabelsAdded(LocationZoneCode, BTreeSet<ZoneLabel>),
+        ZoneLabelsRemoved(LocationZoneCode, BTreeSet<ZoneLabel>),
+        /// A zone of a bulk registration failed to initialize, and is not monitored. The zone is
+        /// unknown to NWS, or failed for the given reason.
+        ZoneRegistrationFailed {
+            zone: LocationZoneCode,
+            unknown_zone: bool,
+            reason: String,
+        },
+        WeatherUpdateStarted {
+            saga_id: String,
+            zones: Vec<LocationZoneCode>,
+            steps: LocationUpdatedSteps,
+        },
+        /// The requested update was folded into the overlapping update in flight.
+        WeatherUpdateCoalesced {
+            saga_id: String,
+            into: String,
+        },
+        WeatherUpdateQueued(QueuedUpdate),
+        WeatherUpdateFinished(String),
     }
 
     impl DomainEvent for RegistrarEvent {
@@ -273,16 +1008,55 @@ mod protocol {
             VERSION.to_string()
         }
     }
+
+    impl VersionedEvent for RegistrarEvent {
+        fn upcasters() -> EventUpcasterRegistry {
+            EventUpcasterRegistry::new().with_upcast(
+                ("forecast_zone_added", "1.0"),
+                ("zone_added", VERSION),
+                upcast_forecast_zone_added,
+            )
+        }
+    }
+
+    /// Upcasts a zone added before zone types were tracked with the type implied by its code.
+    fn upcast_forecast_zone_added(payload: Value) -> Value {
+        let zone = payload
+            .get("ForecastZoneAdded")
+            .and_then(|zone| zone.as_str())
+            .unwrap_or_default();
+        let zone_type = LocationZoneCode::new(zone).implied_zone_type();
+        json!({ "ZoneAdded": [zone, zone_type] })
+    }
 }
 
 mod queries {
-    use crate::model::{LocationZoneCode, Registrar};
-    use cqrs_es::persist::GenericQuery;
-    use cqrs_es::{EventEnvelope, View};
+    use super::{
+        LabelSelector, RegistrarCommand, WatchlistId, WeatherUpdateDisposition, ZoneLabel,
+        REQUESTED_AT_METADATA, REQUESTED_BY_METADATA, WATCHLIST_METADATA,
+    };
+    use crate::model::update::{
+        LocationUpdatedStep, LocationUpdatedSteps, UpdateCompletionStatus, UpdateLocationsCommand,
+        UpdateLocationsId, UpdateLocationsViewProjection,
+    };
+    use crate::model::zone::LocationZoneCommand;
+    use crate::model::{
+        self, CommandEnvelope, LocationZone, LocationZoneCode, LocationZoneType, Registrar,
+        UpdateLocations, UpdateLocationsSaga,
+    };
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use cqrs_es::persist::{GenericQuery, PersistenceError, ViewRepository};
+    use cqrs_es::{EventEnvelope, Query, View};
+    use either::Right;
     use postgres_es::PostgresViewRepository;
     use serde::{Deserialize, Serialize};
-    use std::collections::HashSet;
+    use sql_query_builder as sql;
+    use sqlx::PgPool;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::fmt;
     use std::sync::Arc;
+    use tokio::sync::mpsc;
     use utoipa::ToSchema;
 
     pub const MONITORED_ZONES_QUERY_VIEW: &str = "monitored_zones_query";
@@ -292,10 +1066,153 @@ mod queries {
     pub type MonitoredZonesQuery =
         GenericQuery<MonitoredZonesRepository, MonitoredZonesView, Registrar>;
 
+    /// Lists the watchlists known to the monitored zones projection; i.e., every watchlist that has
+    /// recorded a registrar event.
+    pub async fn list_watchlists(db_pool: &PgPool) -> Result<Vec<WatchlistId>, sqlx::Error> {
+        let select_sql = sql::Select::new()
+            .select("view_id")
+            .from(MONITORED_ZONES_QUERY_VIEW)
+            .order_by("view_id")
+            .to_string();
+
+        let rows: Vec<(String,)> = sqlx::query_as(&select_sql).fetch_all(db_pool).await?;
+        Ok(rows.into_iter().map(|(view_id,)| WatchlistId::new(view_id)).collect())
+    }
+
     #[derive(Debug, Default, Clone, PartialEq, ToSchema, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
     pub struct MonitoredZonesView {
-        pub zones: HashSet<LocationZoneCode>,
+        pub zones: HashMap<LocationZoneCode, MonitoredZone>,
+
+        /// Update sagas the watchlist started and has not yet seen finish.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub updates_in_flight: BTreeMap<String, UpdateInFlight>,
+
+        /// Update requests waiting, in order, for overlapping updates to finish.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub queued_updates: Vec<String>,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UpdateInFlight {
+        pub zones: Vec<LocationZoneCode>,
+
+        #[serde(default)]
+        pub steps: Vec<LocationUpdatedStep>,
+
+        /// Update requests folded into this update.
+        #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+        pub coalesced: BTreeSet<String>,
+    }
+
+    /// Registration and latest update of a zone monitored by the watchlist.
+    #[derive(Debug, Clone, PartialEq, ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MonitoredZone {
+        pub zone_type: LocationZoneType,
+
+        #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+        pub labels: BTreeSet<ZoneLabel>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub added_at: Option<DateTime<Utc>>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub added_by: Option<String>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub last_update: Option<ZoneUpdateSummary>,
+
+        /// Number of update sagas in a row that failed to update the zone, so operators can spot
+        /// zones that keep failing.
+        #[serde(default)]
+        pub consecutive_failures: u32,
+    }
+
+    impl MonitoredZone {
+        fn new(zone_type: LocationZoneType, metadata: &HashMap<String, String>) -> Self {
+            let added_at = metadata
+                .get(REQUESTED_AT_METADATA)
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .map(|at| at.with_timezone(&Utc))
+                .unwrap_or_else(Utc::now);
+
+            Self {
+                zone_type,
+                labels: BTreeSet::new(),
+                added_at: Some(added_at),
+                added_by: metadata.get(REQUESTED_BY_METADATA).cloned(),
+                last_update: None,
+                consecutive_failures: 0,
+            }
+        }
+    }
+
+    /// The last `UpdateLocations` saga touching a zone. The status is empty while the saga is
+    /// updating the zone.
+    #[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ZoneUpdateSummary {
+        pub saga_id: String,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub status: Option<UpdateCompletionStatus>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub completed_at: Option<DateTime<Utc>>,
+    }
+
+    impl MonitoredZonesView {
+        /// Restricts the view to the zones matching the label selector.
+        pub fn select(self, selector: &LabelSelector) -> Self {
+            if selector.is_empty() {
+                return self;
+            }
+
+            let zones = self
+                .zones
+                .into_iter()
+                .filter(|(_, zone)| selector.matches(Some(&zone.labels)))
+                .collect();
+
+            Self {
+                zones,
+                updates_in_flight: self.updates_in_flight,
+                queued_updates: self.queued_updates,
+            }
+        }
+
+        /// Identifies the update serving the requested update, and how the request was handled.
+        /// Unless it was coalesced or queued, the request started its own update.
+        pub fn disposition_of(&self, requested: &str) -> (String, WeatherUpdateDisposition) {
+            let coalesced_into = self
+                .updates_in_flight
+                .iter()
+                .find(|(_, update)| update.coalesced.contains(requested));
+
+            if let Some((running, _)) = coalesced_into {
+                (running.clone(), WeatherUpdateDisposition::Coalesced)
+            } else if self.queued_updates.iter().any(|queued| queued == requested) {
+                (requested.to_string(), WeatherUpdateDisposition::Queued)
+            } else {
+                (requested.to_string(), WeatherUpdateDisposition::Started)
+            }
+        }
+
+        /// Fills in the latest update of each zone, which is kept apart from the view.
+        pub fn with_update_summaries(
+            mut self, mut summaries: HashMap<LocationZoneCode, (ZoneUpdateSummary, u32)>,
+        ) -> Self {
+            for (zone, monitored) in self.zones.iter_mut() {
+                let (last_update, consecutive_failures) = summaries
+                    .remove(zone)
+                    .map_or((None, 0), |(summary, failures)| (Some(summary), failures));
+                monitored.last_update = last_update;
+                monitored.consecutive_failures = consecutive_failures;
+            }
+            self
+        }
     }
 
     impl View<Registrar> for MonitoredZonesView {
@@ -303,8 +1220,9 @@ mod queries {
             use super::RegistrarEvent as Evt;
 
             match &event.payload {
-                Evt::ForecastZoneAdded(zone) => {
-                    self.zones.insert(zone.clone());
+                Evt::ZoneAdded(zone, zone_type) => {
+                    let monitored = MonitoredZone::new(*zone_type, &event.metadata);
+                    self.zones.insert(zone.clone(), monitored);
                 },
                 Evt::ForecastZoneForgotten(zone) => {
                     self.zones.remove(zone);
@@ -312,6 +1230,427 @@ mod queries {
                 Evt::AllForecastZonesForgotten => {
                     self.zones.clear();
                 },
+                Evt::ZoneLabelsAdded(zone, labels) => {
+                    if let Some(monitored) = self.zones.get_mut(zone) {
+                        monitored.labels.extend(labels.iter().cloned());
+                    }
+                },
+                Evt::ZoneLabelsRemoved(zone, labels) => {
+                    if let Some(monitored) = self.zones.get_mut(zone) {
+                        monitored.labels.retain(|label| !labels.contains(label));
+                    }
+                },
+                Evt::ZoneRegistrationFailed { .. } => {},
+                Evt::WeatherUpdateStarted { saga_id, zones, steps } => {
+                    self.queued_updates.retain(|queued| queued != saga_id);
+                    let update = UpdateInFlight {
+                        zones: zones.clone(),
+                        steps: steps.iter().collect(),
+                        ..UpdateInFlight::default()
+                    };
+                    self.updates_in_flight.insert(saga_id.clone(), update);
+                },
+                Evt::WeatherUpdateCoalesced { saga_id, into } => {
+                    if let Some(update) = self.updates_in_flight.get_mut(into) {
+                        update.coalesced.insert(saga_id.clone());
+                    }
+                },
+                Evt::WeatherUpdateQueued(queued) => {
+                    self.queued_updates.push(queued.saga_id.id.to_string());
+                },
+                Evt::WeatherUpdateFinished(saga_id) => {
+                    self.updates_in_flight.remove(saga_id);
+                    self.queued_updates.retain(|queued| queued != saga_id);
+                },
+            }
+        }
+    }
+
+    pub const ZONE_UPDATE_SUMMARIES_TABLE: &str = "zone_update_summaries";
+
+    /// Records the update saga's progress as the latest update of each zone, per the watchlist
+    /// that started the saga. Summaries are kept in their own table rather than the monitored
+    /// zones view, so recording them never contends with the registrar's own view updates.
+    pub struct MonitoredZonesUpdateQuery {
+        db_pool: PgPool,
+        update_locations_view: UpdateLocationsViewProjection,
+    }
+
+    impl MonitoredZonesUpdateQuery {
+        pub fn new(db_pool: PgPool, update_locations_view: UpdateLocationsViewProjection) -> Self {
+            Self { db_pool, update_locations_view }
+        }
+
+        async fn do_dispatch(
+            &self, saga_id: &str, events: &[EventEnvelope<UpdateLocations>],
+        ) -> Result<(), PersistenceError> {
+            use crate::model::update::UpdateLocationsEvent as Evt;
+
+            let watchlist =
+                match watchlist_of_update(&self.update_locations_view, saga_id, events).await? {
+                    Some(watchlist) => watchlist,
+                    None => return Ok(()),
+                };
+
+            for event in events {
+                let at = model::occurred_at(&event.metadata);
+                let recorded = match &event.payload {
+                    Evt::StartedSteps(_, zones, _) => {
+                        self.record_started(&watchlist, saga_id, zones).await
+                    },
+                    Evt::LocationUpdated(zone, Right(status)) => {
+                        self.record_completed(&watchlist, saga_id, zone, *status, at).await
+                    },
+                    Evt::TimedOut(unfinished) => {
+                        self.record_timed_out(&watchlist, saga_id, unfinished.keys(), at).await
+                    },
+                    Evt::Cancelled(_) => self.record_cancelled(&watchlist, saga_id, at).await,
+                    _ => Ok(()),
+                };
+
+                recorded.map_err(|err| PersistenceError::ConnectionError(Box::new(err)))?;
+            }
+
+            Ok(())
+        }
+
+        async fn record_started(
+            &self, watchlist: &str, saga_id: &str, zones: &[LocationZoneCode],
+        ) -> Result<(), sqlx::Error> {
+            let insert_sql = sql::Insert::new()
+                .insert_into(&format!(
+                    "{ZONE_UPDATE_SUMMARIES_TABLE} (watchlist, zone_code, saga_id)"
+                ))
+                .values("($1, $2, $3)")
+                .on_conflict(
+                    "(watchlist, zone_code) DO UPDATE SET saga_id = EXCLUDED.saga_id, status = \
+                     NULL, completed_at = NULL",
+                )
+                .to_string();
+
+            for zone in zones {
+                sqlx::query(&insert_sql)
+                    .bind(watchlist)
+                    .bind(zone.as_ref())
+                    .bind(saga_id)
+                    .execute(&self.db_pool)
+                    .await?;
+            }
+            Ok(())
+        }
+
+        /// Records the zone's update as completed, counting the zone's consecutive failures.
+        async fn record_completed(
+            &self, watchlist: &str, saga_id: &str, zone: &LocationZoneCode,
+            status: UpdateCompletionStatus, completed_at: DateTime<Utc>,
+        ) -> Result<(), sqlx::Error> {
+            let insert_sql = sql::Insert::new()
+                .insert_into(&format!(
+                    "{ZONE_UPDATE_SUMMARIES_TABLE} (watchlist, zone_code, saga_id, status, \
+                     completed_at, consecutive_failures)"
+                ))
+                .values("($1, $2, $3, $4, $5, $6)")
+                .on_conflict(&format!(
+                    "(watchlist, zone_code) DO UPDATE SET saga_id = EXCLUDED.saga_id, status = \
+                     EXCLUDED.status, completed_at = EXCLUDED.completed_at, consecutive_failures \
+                     = CASE EXCLUDED.status WHEN '{succeeded}' THEN 0 WHEN '{failed}' THEN \
+                     {ZONE_UPDATE_SUMMARIES_TABLE}.consecutive_failures + 1 ELSE \
+                     {ZONE_UPDATE_SUMMARIES_TABLE}.consecutive_failures END",
+                    succeeded = UpdateCompletionStatus::Succeeded,
+                    failed = UpdateCompletionStatus::Failed,
+                ))
+                .to_string();
+
+            let failures = i32::from(status == UpdateCompletionStatus::Failed);
+            sqlx::query(&insert_sql)
+                .bind(watchlist)
+                .bind(zone.as_ref())
+                .bind(saga_id)
+                .bind(status.to_string())
+                .bind(completed_at)
+                .bind(failures)
+                .execute(&self.db_pool)
+                .await?;
+            Ok(())
+        }
+
+        /// Records the zones unfinished when the update timed out as failed by it.
+        async fn record_timed_out<'z>(
+            &self, watchlist: &str, saga_id: &str,
+            unfinished: impl Iterator<Item = &'z LocationZoneCode>, timed_out_at: DateTime<Utc>,
+        ) -> Result<(), sqlx::Error> {
+            let failed = UpdateCompletionStatus::Failed;
+            for zone in unfinished {
+                self.record_completed(watchlist, saga_id, zone, failed, timed_out_at)
+                    .await?;
+            }
+            Ok(())
+        }
+
+        /// Records the zones yet to finish the cancelled update as skipped by it.
+        async fn record_cancelled(
+            &self, watchlist: &str, saga_id: &str, cancelled_at: DateTime<Utc>,
+        ) -> Result<(), sqlx::Error> {
+            let update_sql = sql::Update::new()
+                .update(ZONE_UPDATE_SUMMARIES_TABLE)
+                .set("status = $3, completed_at = $4")
+                .where_clause("watchlist = $1")
+                .where_clause("saga_id = $2")
+                .where_clause("status IS NULL")
+                .to_string();
+
+            sqlx::query(&update_sql)
+                .bind(watchlist)
+                .bind(saga_id)
+                .bind(UpdateCompletionStatus::Skipped.to_string())
+                .bind(cancelled_at)
+                .execute(&self.db_pool)
+                .await?;
+            Ok(())
+        }
+    }
+
+    /// Loads the latest update of each zone the watchlist updated, with the number of updates in
+    /// a row that failed to update the zone.
+    pub async fn load_zone_update_summaries(
+        db_pool: &PgPool, watchlist: &WatchlistId,
+    ) -> Result<HashMap<LocationZoneCode, (ZoneUpdateSummary, u32)>, sqlx::Error> {
+        let select_sql = sql::Select::new()
+            .select("zone_code, saga_id, status, completed_at, consecutive_failures")
+            .from(ZONE_UPDATE_SUMMARIES_TABLE)
+            .where_clause("watchlist = $1")
+            .to_string();
+
+        let rows: Vec<(String, String, Option<String>, Option<DateTime<Utc>>, i32)> =
+            sqlx::query_as(&select_sql)
+                .bind(watchlist.as_ref())
+                .fetch_all(db_pool)
+                .await?;
+
+        let summaries = rows
+            .into_iter()
+            .map(|(zone, saga_id, status, completed_at, failures)| {
+                let status = status.and_then(|status| status.parse().ok());
+                let summary = ZoneUpdateSummary { saga_id, status, completed_at };
+                let failures = u32::try_from(failures).unwrap_or_default();
+                (LocationZoneCode::new(zone), (summary, failures))
+            })
+            .collect();
+
+        Ok(summaries)
+    }
+
+    /// Loads the watchlist's monitored zones along with the latest update of each zone.
+    pub async fn load_monitored_zones(
+        watchlist: &WatchlistId, monitored_zones_view: &MonitoredZonesViewProjection,
+        db_pool: &PgPool,
+    ) -> Result<Option<MonitoredZonesView>, PersistenceError> {
+        let view = match monitored_zones_view.load(watchlist.as_ref()).await? {
+            Some(view) => view,
+            None => return Ok(None),
+        };
+
+        let summaries = load_zone_update_summaries(db_pool, watchlist)
+            .await
+            .map_err(|err| PersistenceError::ConnectionError(Box::new(err)))?;
+        Ok(Some(view.with_update_summaries(summaries)))
+    }
+
+    /// Finds the watchlist that started the update saga, from the events' metadata or else from
+    /// the saga's view.
+    async fn watchlist_of_update(
+        update_locations_view: &UpdateLocationsViewProjection, saga_id: &str,
+        events: &[EventEnvelope<UpdateLocations>],
+    ) -> Result<Option<String>, PersistenceError> {
+        let started_on = events
+            .iter()
+            .find_map(|envelope| envelope.metadata.get(WATCHLIST_METADATA).cloned());
+        if started_on.is_some() {
+            return Ok(started_on);
+        }
+
+        let view = update_locations_view.load(saga_id).await?;
+        Ok(view.and_then(|v| v.watchlist).map(String::from))
+    }
+
+    impl fmt::Debug for MonitoredZonesUpdateQuery {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("MonitoredZonesUpdateQuery").finish()
+        }
+    }
+
+    #[async_trait]
+    impl Query<UpdateLocations> for MonitoredZonesUpdateQuery {
+        async fn dispatch(&self, saga_id: &str, events: &[EventEnvelope<UpdateLocations>]) {
+            if let Err(error) = self.do_dispatch(saga_id, events).await {
+                tracing::error!(?error, %saga_id, "monitored zones update query failed");
+            }
+        }
+    }
+
+    /// Notes to the watchlist registrar when an update saga it started finishes, via the
+    /// registrar's command relay.
+    pub struct UpdateFinishedNotifier {
+        registrar_tx: mpsc::Sender<CommandEnvelope<Registrar>>,
+        update_locations_view: UpdateLocationsViewProjection,
+    }
+
+    impl UpdateFinishedNotifier {
+        pub fn new(
+            registrar_tx: mpsc::Sender<CommandEnvelope<Registrar>>,
+            update_locations_view: UpdateLocationsViewProjection,
+        ) -> Self {
+            Self { registrar_tx, update_locations_view }
+        }
+
+        async fn do_dispatch(
+            &self, saga_id: &str, events: &[EventEnvelope<UpdateLocations>],
+        ) -> Result<(), PersistenceError> {
+            let is_finished = events.iter().any(|envelope| envelope.payload.is_finished());
+            if !is_finished {
+                return Ok(());
+            }
+
+            let watchlist =
+                watchlist_of_update(&self.update_locations_view, saga_id, events).await?;
+            if let Some(watchlist) = watchlist {
+                let command = RegistrarCommand::NoteWeatherUpdateFinished(saga_id.to_string());
+                if let Err(error) =
+                    self.registrar_tx.send(CommandEnvelope::new(watchlist, command)).await
+                {
+                    tracing::error!(
+                        ?error, %saga_id,
+                        "failed to note finished update to registrar"
+                    );
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl fmt::Debug for UpdateFinishedNotifier {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("UpdateFinishedNotifier").finish()
+        }
+    }
+
+    #[async_trait]
+    impl Query<UpdateLocations> for UpdateFinishedNotifier {
+        async fn dispatch(&self, saga_id: &str, events: &[EventEnvelope<UpdateLocations>]) {
+            if let Err(error) = self.do_dispatch(saga_id, events).await {
+                tracing::error!(?error, %saga_id, "update finished notifier failed");
+            }
+        }
+    }
+
+    /// Starts the update saga of each weather update once the registrar commits its start, so a
+    /// saga only runs for an update the registrar has recorded in flight. An update whose saga
+    /// fails to start is noted finished, releasing the updates queued behind it.
+    pub struct WeatherUpdateStarter {
+        update_saga: UpdateLocationsSaga,
+        registrar_tx: mpsc::Sender<CommandEnvelope<Registrar>>,
+    }
+
+    impl WeatherUpdateStarter {
+        pub fn new(
+            update_saga: UpdateLocationsSaga,
+            registrar_tx: mpsc::Sender<CommandEnvelope<Registrar>>,
+        ) -> Self {
+            Self { update_saga, registrar_tx }
+        }
+
+        async fn start_saga(
+            &self, watchlist: &str, saga_id: &str, zones: &[LocationZoneCode],
+            steps: LocationUpdatedSteps,
+        ) {
+            let metadata = model::with_occurred_at(maplit::hashmap! {
+                model::CORRELATION_METADATA.to_string() => saga_id.to_string(),
+                WATCHLIST_METADATA.to_string() => watchlist.to_string(),
+            });
+            let command = UpdateLocationsCommand::UpdateLocations(
+                UpdateLocationsId::for_labeled(saga_id.to_string()),
+                zones.to_vec(),
+                steps,
+            );
+
+            let outcome = self.update_saga.execute_with_metadata(saga_id, command, metadata).await;
+            if let Err(error) = outcome {
+                tracing::error!(?error, %watchlist, %saga_id, "failed to start weather update");
+                let command = RegistrarCommand::NoteWeatherUpdateFinished(saga_id.to_string());
+                if let Err(error) =
+                    self.registrar_tx.send(CommandEnvelope::new(watchlist, command)).await
+                {
+                    tracing::error!(
+                        ?error, %saga_id,
+                        "failed to note unstarted update to registrar"
+                    );
+                }
+            }
+        }
+    }
+
+    impl fmt::Debug for WeatherUpdateStarter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("WeatherUpdateStarter").finish()
+        }
+    }
+
+    #[async_trait]
+    impl Query<Registrar> for WeatherUpdateStarter {
+        async fn dispatch(&self, watchlist: &str, events: &[EventEnvelope<Registrar>]) {
+            for envelope in events {
+                if let super::RegistrarEvent::WeatherUpdateStarted { saga_id, zones, steps } =
+                    &envelope.payload
+                {
+                    self.start_saga(watchlist, saga_id, zones, *steps).await;
+                }
+            }
+        }
+    }
+
+    /// Notes the zones a watchlist adds or forgets with their `LocationZone`s once the registrar
+    /// commits the change. A zone retires when the last watchlist monitoring it forgets it.
+    pub struct ZoneWatchNotifier {
+        location_tx: mpsc::Sender<CommandEnvelope<LocationZone>>,
+    }
+
+    impl ZoneWatchNotifier {
+        pub fn new(location_tx: mpsc::Sender<CommandEnvelope<LocationZone>>) -> Self {
+            Self { location_tx }
+        }
+    }
+
+    impl fmt::Debug for ZoneWatchNotifier {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ZoneWatchNotifier").finish()
+        }
+    }
+
+    #[async_trait]
+    impl Query<Registrar> for ZoneWatchNotifier {
+        async fn dispatch(&self, watchlist: &str, events: &[EventEnvelope<Registrar>]) {
+            use super::RegistrarEvent as Evt;
+
+            for envelope in events {
+                let (zone, command) = match &envelope.payload {
+                    Evt::ZoneAdded(zone, _) => {
+                        (zone, LocationZoneCommand::Watch(watchlist.to_string()))
+                    },
+                    Evt::ForecastZoneForgotten(zone) => {
+                        (zone, LocationZoneCommand::Unwatch(watchlist.to_string()))
+                    },
+                    _ => continue,
+                };
+
+                let command = CommandEnvelope::new_with_metadata(
+                    zone.as_ref(),
+                    command,
+                    model::occurrence_metadata(),
+                );
+                if let Err(error) = self.location_tx.send(command).await {
+                    tracing::error!(?error, %watchlist, %zone, "failed to note zone watch change");
+                }
             }
         }
     }
@@ -332,5 +1671,337 @@ mod errors {
 
         #[error("rejected registrar command: {0}")]
         RejectedCommand(String),
+
+        #[error("weather update {0} already in progress for requested zones")]
+        UpdateInProgress(String),
+
+        #[error("nothing to update: {0}")]
+        NothingToUpdate(String),
+
+        #[error("invalid watchlist identifier: {0:?}")]
+        InvalidWatchlist(String),
+
+        #[error("failed registrar query: {0}")]
+        Sql(#[from] sqlx::Error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_label_selector_matches() {
+        let selector = LabelSelector::parse(" region:mid-atlantic, customer:acme,,");
+        assert_eq!(
+            selector,
+            LabelSelector::new(["customer:acme", "region:mid-atlantic"])
+        );
+
+        let labels: BTreeSet<_> = ["customer:acme", "region:mid-atlantic", "tier:gold"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(selector.matches(Some(&labels)));
+        assert!(!LabelSelector::new(["customer:zenith"]).matches(Some(&labels)));
+        assert!(!selector.matches(None));
+        assert!(LabelSelector::default().matches(None));
+    }
+
+    #[test]
+    fn test_watchlist_id_validation() {
+        use claim::{assert_err, assert_ok};
+
+        let id = assert_ok!(WatchlistId::parse("team-west_2.prod"));
+        assert_eq!(id.as_ref(), "team-west_2.prod");
+        assert_ok!(WatchlistId::parse("a".repeat(MAX_WATCHLIST_ID_LEN)));
+
+        assert_err!(WatchlistId::parse(""));
+        assert_err!(WatchlistId::parse("a".repeat(MAX_WATCHLIST_ID_LEN + 1)));
+        assert_err!(WatchlistId::parse("team west"));
+        assert_err!(WatchlistId::parse("team/west"));
+
+        let id: WatchlistId = assert_ok!(serde_json::from_str(r#""default""#));
+        assert_eq!(id, WatchlistId::default());
+        assert_eq!(assert_ok!(serde_json::to_string(&id)), r#""default""#);
+        assert_err!(serde_json::from_str::<WatchlistId>(r#""bad id""#));
+    }
+
+    #[test]
+    fn test_monitored_zones_view_records_registration() {
+        use cqrs_es::{EventEnvelope, View};
+
+        let zone = LocationZoneCode::new("WAZ558");
+        let envelope = |sequence, payload| EventEnvelope::<Registrar> {
+            aggregate_id: DEFAULT_WATCHLIST_ID.to_string(),
+            sequence,
+            payload,
+            metadata: maplit::hashmap! {
+                REQUESTED_BY_METADATA.to_string() => "ops".to_string(),
+                REQUESTED_AT_METADATA.to_string() => "2023-03-15T12:00:00Z".to_string(),
+            },
+        };
+
+        let mut view = MonitoredZonesView::default();
+        view.update(&envelope(
+            1,
+            RegistrarEvent::ZoneAdded(zone.clone(), LocationZoneType::Forecast),
+        ));
+        view.update(&envelope(
+            2,
+            RegistrarEvent::ZoneLabelsAdded(zone.clone(), ["tier:gold".to_string()].into()),
+        ));
+
+        let monitored = &view.zones[&zone];
+        assert_eq!(monitored.added_by.as_deref(), Some("ops"));
+        assert_eq!(
+            monitored.added_at.map(|at| at.to_rfc3339()),
+            Some("2023-03-15T12:00:00+00:00".to_string())
+        );
+        assert_eq!(monitored.consecutive_failures, 0);
+        assert_eq!(
+            view.clone().select(&LabelSelector::parse("tier:gold")),
+            view
+        );
+        assert!(view.select(&LabelSelector::parse("tier:silver")).zones.is_empty());
+    }
+
+    #[test]
+    fn test_monitored_zones_view_with_update_summaries() {
+        use crate::model::update::UpdateCompletionStatus;
+
+        use cqrs_es::{EventEnvelope, View};
+
+        let updated = LocationZoneCode::new("WAZ558");
+        let never_updated = LocationZoneCode::new("WAZ559");
+        let mut view = MonitoredZonesView::default();
+        for (sequence, zone) in [(1, &updated), (2, &never_updated)] {
+            view.update(&EventEnvelope::<Registrar> {
+                aggregate_id: DEFAULT_WATCHLIST_ID.to_string(),
+                sequence,
+                payload: RegistrarEvent::ZoneAdded(zone.clone(), LocationZoneType::Forecast),
+                metadata: HashMap::new(),
+            });
+        }
+
+        let summary = ZoneUpdateSummary {
+            saga_id: "update-1".to_string(),
+            status: Some(UpdateCompletionStatus::Failed),
+            completed_at: None,
+        };
+        let view = view.with_update_summaries(HashMap::from([
+            (updated.clone(), (summary.clone(), 2)),
+            (LocationZoneCode::new("WAZ999"), (summary.clone(), 1)),
+        ]));
+
+        assert_eq!(view.zones.len(), 2);
+        assert_eq!(view.zones[&updated].last_update, Some(summary));
+        assert_eq!(view.zones[&updated].consecutive_failures, 2);
+        assert_eq!(view.zones[&never_updated].last_update, None);
+        assert_eq!(view.zones[&never_updated].consecutive_failures, 0);
+
+        let status: UpdateCompletionStatus = claim::assert_ok!("Skipped".parse());
+        assert_eq!(status, UpdateCompletionStatus::Skipped);
+    }
+
+    #[test]
+    fn test_update_weather_coalesces_into_update_in_flight() {
+        let zone = LocationZoneCode::new("WAZ558");
+        let saga_id = crate::model::update::generate_id();
+
+        cqrs_es::test::TestFramework::<Registrar>::with(RegistrarServices::HappyPath(
+            HappyPathServices,
+        ))
+        .given(vec![
+            RegistrarEvent::ZoneAdded(zone.clone(), LocationZoneType::Forecast),
+            RegistrarEvent::WeatherUpdateStarted {
+                saga_id: "running".to_string(),
+                zones: vec![zone],
+                steps: LocationUpdatedSteps::all(),
+            },
+        ])
+        .when(RegistrarCommand::UpdateWeather {
+            saga_id: saga_id.clone(),
+            watchlist: WatchlistId::default(),
+            scope: UpdateScope::default(),
+        })
+        .then_expect_events(vec![RegistrarEvent::WeatherUpdateCoalesced {
+            saga_id: saga_id.id.to_string(),
+            into: "running".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_update_weather_without_zones_rejected() {
+        cqrs_es::test::TestFramework::<Registrar>::with(RegistrarServices::HappyPath(
+            HappyPathServices,
+        ))
+        .given_no_previous_events()
+        .when(RegistrarCommand::UpdateWeather {
+            saga_id: crate::model::update::generate_id(),
+            watchlist: WatchlistId::default(),
+            scope: UpdateScope::default(),
+        })
+        .then_expect_error_message("nothing to update: no monitored zone or update step in scope");
+    }
+
+    #[test]
+    fn test_update_weather_starts_alongside_update_of_other_steps() {
+        use crate::model::update::LocationUpdatedStep;
+
+        let zone = LocationZoneCode::new("WAZ558");
+        let saga_id = crate::model::update::generate_id();
+
+        cqrs_es::test::TestFramework::<Registrar>::with(RegistrarServices::HappyPath(
+            HappyPathServices,
+        ))
+        .given(vec![
+            RegistrarEvent::ZoneAdded(zone.clone(), LocationZoneType::Forecast),
+            RegistrarEvent::WeatherUpdateStarted {
+                saga_id: "running".to_string(),
+                zones: vec![zone.clone()],
+                steps: LocationUpdatedStep::Forecast.into(),
+            },
+        ])
+        .when(RegistrarCommand::UpdateWeather {
+            saga_id: saga_id.clone(),
+            watchlist: WatchlistId::default(),
+            scope: UpdateScope {
+                zones: Some(vec![zone.clone()]),
+                steps: LocationUpdatedStep::Alert.into(),
+                ..UpdateScope::default()
+            },
+        })
+        .then_expect_events(vec![RegistrarEvent::WeatherUpdateStarted {
+            saga_id: saga_id.id.to_string(),
+            zones: vec![zone],
+            steps: LocationUpdatedStep::Alert.into(),
+        }]);
+    }
+
+    #[test]
+    fn test_finished_update_starts_one_of_overlapping_queued_updates() {
+        let zone = LocationZoneCode::new("WAZ558");
+        let queued = |saga_id| QueuedUpdate {
+            saga_id,
+            watchlist: WatchlistId::default(),
+            scope: UpdateScope::default(),
+        };
+        let first = crate::model::update::generate_id();
+        let second = crate::model::update::generate_id();
+
+        cqrs_es::test::TestFramework::<Registrar>::with(RegistrarServices::HappyPath(
+            HappyPathServices,
+        ))
+        .given(vec![
+            RegistrarEvent::ZoneAdded(zone.clone(), LocationZoneType::Forecast),
+            RegistrarEvent::WeatherUpdateStarted {
+                saga_id: "running".to_string(),
+                zones: vec![zone.clone()],
+                steps: LocationUpdatedSteps::all(),
+            },
+            RegistrarEvent::WeatherUpdateQueued(queued(first.clone())),
+            RegistrarEvent::WeatherUpdateQueued(queued(second)),
+        ])
+        .when(RegistrarCommand::NoteWeatherUpdateFinished(
+            "running".to_string(),
+        ))
+        .then_expect_events(vec![
+            RegistrarEvent::WeatherUpdateFinished("running".to_string()),
+            RegistrarEvent::WeatherUpdateStarted {
+                saga_id: first.id.to_string(),
+                zones: vec![zone],
+                steps: LocationUpdatedSteps::all(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_monitor_forecast_zones_adds_each_unmonitored_zone_once() {
+        cqrs_es::test::TestFramework::<Registrar>::with(RegistrarServices::HappyPath(
+            HappyPathServices,
+        ))
+        .given(vec![RegistrarEvent::ZoneAdded(
+            LocationZoneCode::new("WAZ558"),
+            LocationZoneType::Forecast,
+        )])
+        .when(RegistrarCommand::MonitorForecastZones(vec![
+            (LocationZoneCode::new("WAZ558"), LocationZoneType::Forecast),
+            (LocationZoneCode::new("WAC033"), LocationZoneType::County),
+            (LocationZoneCode::new("WAC033"), LocationZoneType::County),
+            (LocationZoneCode::new("MDZ009"), LocationZoneType::Public),
+        ]))
+        .then_expect_events(vec![
+            RegistrarEvent::ZoneAdded(LocationZoneCode::new("WAC033"), LocationZoneType::County),
+            RegistrarEvent::ZoneAdded(LocationZoneCode::new("MDZ009"), LocationZoneType::Public),
+        ]);
+    }
+
+    #[test]
+    fn test_clear_zone_monitoring_forgets_each_zone() {
+        cqrs_es::test::TestFramework::<Registrar>::with(RegistrarServices::HappyPath(
+            HappyPathServices,
+        ))
+        .given(vec![
+            RegistrarEvent::ZoneAdded(LocationZoneCode::new("WAZ558"), LocationZoneType::Forecast),
+            RegistrarEvent::ZoneAdded(LocationZoneCode::new("WAC033"), LocationZoneType::County),
+        ])
+        .when(RegistrarCommand::ClearZoneMonitoring)
+        .then_expect_events(vec![
+            RegistrarEvent::ForecastZoneForgotten(LocationZoneCode::new("WAC033")),
+            RegistrarEvent::ForecastZoneForgotten(LocationZoneCode::new("WAZ558")),
+        ]);
+    }
+
+    #[test]
+    fn test_registration_report_per_zone_outcomes() {
+        let codes = ["WAZ558", "WAC033", "nope", "WAZ999", "WAZ558", "WAZ001"];
+        let events = vec![
+            RegistrarEvent::ZoneAdded(LocationZoneCode::new("WAZ558"), LocationZoneType::Forecast),
+            RegistrarEvent::ZoneRegistrationFailed {
+                zone: LocationZoneCode::new("WAZ999"),
+                unknown_zone: true,
+                reason: "unknown zone".to_string(),
+            },
+            RegistrarEvent::ZoneRegistrationFailed {
+                zone: LocationZoneCode::new("WAZ001"),
+                unknown_zone: false,
+                reason: "noaa unavailable".to_string(),
+            },
+        ];
+
+        let report = bulk::registration_report(codes.map(String::from).to_vec(), &events);
+        let outcomes: Vec<_> = report
+            .iter()
+            .map(|r| (r.zone.as_str(), r.outcome, r.reason.as_deref()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("WAZ558", ZoneRegistrationOutcome::Added, None),
+                ("WAC033", ZoneRegistrationOutcome::AlreadyMonitored, None),
+                (
+                    "nope",
+                    ZoneRegistrationOutcome::Invalid,
+                    Some("not a NWS zone code; e.g., WAZ558 or WAC033")
+                ),
+                (
+                    "WAZ999",
+                    ZoneRegistrationOutcome::Invalid,
+                    Some("unknown zone")
+                ),
+                (
+                    "WAZ558",
+                    ZoneRegistrationOutcome::Duplicate,
+                    Some("repeats an earlier zone code of the request")
+                ),
+                (
+                    "WAZ001",
+                    ZoneRegistrationOutcome::Failed,
+                    Some("noaa unavailable")
+                ),
+            ]
+        );
     }
 }
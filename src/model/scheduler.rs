@@ -0,0 +1,274 @@
+use crate::model::registrar::{
+    self, MonitoredZonesViewProjection, RegistrarError, UpdateScope, WatchlistId,
+};
+use crate::model::RegistrarAggregate;
+use crate::settings::UpdateSchedulerSettings;
+use chrono::{DateTime, Utc};
+use cqrs_es::persist::ViewRepository;
+use cqrs_es::AggregateError;
+use rand::Rng;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use utoipa::ToSchema;
+
+/// Requester recorded against the registrar commands issued by the scheduler.
+const SCHEDULER_REQUESTER: &str = "scheduler";
+
+pub type UpdateScheduleStatusRef = Arc<RwLock<UpdateScheduleStatus>>;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateScheduleStatus {
+    pub enabled: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_run: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<DateTime<Utc>>,
+
+    /// Update saga started by the last run, per watchlist.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub last_saga_ids: BTreeMap<WatchlistId, String>,
+
+    pub skipped_runs: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum UpdateSchedule {
+    Interval(Duration),
+    Cron(Box<cron::Schedule>),
+}
+
+impl UpdateSchedule {
+    pub fn from_settings(settings: &UpdateSchedulerSettings) -> Result<Self, cron::error::Error> {
+        match settings.cron.as_deref() {
+            Some(expression) => {
+                cron::Schedule::from_str(expression).map(|schedule| Self::Cron(Box::new(schedule)))
+            },
+            None => Ok(Self::Interval(settings.interval)),
+        }
+    }
+
+    fn next_after(&self, last: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Interval(interval) => {
+                chrono::Duration::from_std(*interval).ok().map(|i| last + i)
+            },
+            Self::Cron(schedule) => schedule.after(&last).next(),
+        }
+    }
+
+    /// The slot following the last scheduled run, unless that slot has already passed; e.g.,
+    /// after a run overran or the process stalled. Then the next slot is taken from now, so
+    /// missed runs are dropped rather than fired back-to-back.
+    fn next_slot(&self, last: DateTime<Utc>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self.next_after(last) {
+            Some(next) if next < now => self.next_after(last.max(now)),
+            next => next,
+        }
+    }
+}
+
+/// Periodically requests a weather update of every watchlist, in place of an external caller
+/// hitting the update endpoint.
+pub struct UpdateWeatherScheduler {
+    schedule: UpdateSchedule,
+    jitter: Duration,
+    skip_if_running: bool,
+    registrar: RegistrarAggregate,
+    monitored_zones_view: MonitoredZonesViewProjection,
+    db_pool: PgPool,
+    status: UpdateScheduleStatusRef,
+}
+
+impl fmt::Debug for UpdateWeatherScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateWeatherScheduler")
+            .field("schedule", &self.schedule)
+            .field("jitter", &self.jitter)
+            .field("skip_if_running", &self.skip_if_running)
+            .finish()
+    }
+}
+
+impl UpdateWeatherScheduler {
+    pub fn new(
+        settings: &UpdateSchedulerSettings, registrar: RegistrarAggregate,
+        monitored_zones_view: MonitoredZonesViewProjection, db_pool: PgPool,
+    ) -> Result<Self, cron::error::Error> {
+        let schedule = UpdateSchedule::from_settings(settings)?;
+        let description = settings
+            .cron
+            .clone()
+            .unwrap_or_else(|| format!("every {:?}", settings.interval));
+        let status = UpdateScheduleStatus {
+            enabled: true,
+            schedule: Some(description),
+            ..UpdateScheduleStatus::default()
+        };
+
+        Ok(Self {
+            schedule,
+            jitter: settings.jitter,
+            skip_if_running: settings.skip_if_running,
+            registrar,
+            monitored_zones_view,
+            db_pool,
+            status: Arc::new(RwLock::new(status)),
+        })
+    }
+
+    pub fn status(&self) -> UpdateScheduleStatusRef {
+        self.status.clone()
+    }
+
+    pub fn run(self) -> JoinHandle<()> {
+        tokio::spawn(async move { self.do_run().await })
+    }
+
+    async fn do_run(self) {
+        let mut last_scheduled = Utc::now();
+
+        loop {
+            let scheduled = match self.schedule.next_slot(last_scheduled, Utc::now()) {
+                Some(next) => next,
+                None => {
+                    tracing::info!(schedule=?self.schedule, "update schedule exhausted - stopping");
+                    self.status.write().await.next_run = None;
+                    break;
+                },
+            };
+
+            let next_run = scheduled + self.pick_jitter();
+            self.status.write().await.next_run = Some(next_run);
+
+            let delay = (next_run - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+            tracing::debug!(%next_run, ?delay, "waiting for next scheduled weather update");
+            tokio::time::sleep(delay).await;
+
+            // schedule from the unjittered time so jitter does not accumulate into drift
+            last_scheduled = scheduled;
+            self.do_scheduled_update().await;
+        }
+    }
+
+    fn pick_jitter(&self) -> chrono::Duration {
+        let max_jitter_millis = u64::try_from(self.jitter.as_millis()).unwrap_or(u64::MAX);
+        if max_jitter_millis == 0 {
+            return chrono::Duration::zero();
+        }
+
+        let jitter_millis = rand::thread_rng().gen_range(0..=max_jitter_millis);
+        chrono::Duration::from_std(Duration::from_millis(jitter_millis))
+            .unwrap_or_else(|_| chrono::Duration::zero())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn do_scheduled_update(&self) {
+        let watchlists = match registrar::list_watchlists(&self.db_pool).await {
+            Ok(watchlists) => watchlists,
+            Err(error) => {
+                tracing::error!(
+                    ?error,
+                    "failed to list watchlists for scheduled weather update"
+                );
+                return;
+            },
+        };
+
+        for watchlist in watchlists {
+            self.do_update_watchlist(watchlist).await;
+        }
+
+        self.status.write().await.last_run = Some(Utc::now());
+    }
+
+    async fn do_update_watchlist(&self, watchlist: WatchlistId) {
+        if self.skip_if_running && self.is_update_pending(&watchlist).await {
+            tracing::info!(
+                %watchlist,
+                "weather update of watchlist still in flight or queued - skipping run"
+            );
+            self.status.write().await.skipped_runs += 1;
+            return;
+        }
+
+        let outcome = registrar::request_weather_update(
+            &watchlist,
+            UpdateScope::default(),
+            registrar::request_metadata(SCHEDULER_REQUESTER),
+            &self.registrar,
+            &self.monitored_zones_view,
+        )
+        .await;
+
+        match outcome {
+            Ok(request) => {
+                tracing::info!(%watchlist, ?request, "scheduled weather update requested");
+                let mut status = self.status.write().await;
+                status.last_saga_ids.insert(watchlist, request.update_id);
+            },
+            Err(AggregateError::UserError(RegistrarError::NothingToUpdate(reason))) => {
+                tracing::info!(%watchlist, %reason, "nothing to update in scheduled weather update")
+            },
+            Err(error) => {
+                tracing::error!(?error, %watchlist, "scheduled weather update failed")
+            },
+        }
+    }
+
+    /// Whether an update of the watchlist is in flight or queued, whoever requested it.
+    async fn is_update_pending(&self, watchlist: &WatchlistId) -> bool {
+        match self.monitored_zones_view.load(watchlist.as_ref()).await {
+            Ok(view) => view.map_or(false, |v| {
+                !v.updates_in_flight.is_empty() || !v.queued_updates.is_empty()
+            }),
+            Err(error) => {
+                tracing::warn!(
+                    ?error,
+                    "failed to load pending updates of watchlist: {watchlist}"
+                );
+                false
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_next_slot_drops_missed_runs() {
+        let last = Utc.with_ymd_and_hms(2023, 3, 15, 12, 0, 0).unwrap();
+        let minute = chrono::Duration::minutes(1);
+        let interval = UpdateSchedule::Interval(Duration::from_secs(60));
+        assert_eq!(interval.next_slot(last, last), Some(last + minute));
+        assert_eq!(
+            interval.next_slot(last, last + minute * 5),
+            Some(last + minute * 6)
+        );
+
+        let cron = UpdateSchedule::Cron(Box::new(claim::assert_ok!(cron::Schedule::from_str(
+            "0 * * * * *"
+        ))));
+        assert_eq!(cron.next_slot(last, last), Some(last + minute));
+        assert_eq!(
+            cron.next_slot(last, last + minute * 5 + chrono::Duration::seconds(30)),
+            Some(last + minute * 6)
+        );
+    }
+}
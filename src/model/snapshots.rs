@@ -0,0 +1,161 @@
+use crate::model::{LocationZone, Registrar, UpdateLocations, VersionedEvent};
+use crate::settings::SnapshotSettings;
+use cqrs_es::persist::PersistedEventStore;
+use cqrs_es::{Aggregate, CqrsFramework, EventStore, Query};
+use postgres_es::{PostgresCqrs, PostgresEventRepository};
+use sql_query_builder as sql;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use thiserror::Error;
+
+pub const SNAPSHOT_TABLE: &str = "snapshots";
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("failed to access aggregate snapshots: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("failed to replay {aggregate_type} aggregate {aggregate_id}: {message}")]
+    Replay {
+        aggregate_type: String,
+        aggregate_id: String,
+        message: String,
+    },
+
+    #[error("failed to serialize aggregate snapshot: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Makes the aggregate's CQRS framework. Given a snapshot interval, aggregates load from their
+/// latest snapshot, taken every interval of events; otherwise, they replay their full event
+/// stream. Stored events from past versions are upcast as they load.
+pub fn make_postgres_cqrs<A>(
+    db_pool: PgPool, queries: Vec<Box<dyn Query<A>>>, services: A::Services,
+    snapshot_interval: Option<usize>,
+) -> PostgresCqrs<A>
+where
+    A: Aggregate,
+    A::Event: VersionedEvent,
+{
+    let repo = PostgresEventRepository::new(db_pool);
+    let store = match snapshot_interval.filter(|interval| 0 < *interval) {
+        Some(interval) => PersistedEventStore::new_snapshot_store(repo, interval),
+        None => PersistedEventStore::new_event_store(repo),
+    };
+
+    let store = store.with_upcasters(vec![Box::new(A::Event::upcasters())]);
+    CqrsFramework::new(store, queries, services)
+}
+
+/// Rebuilds the snapshots of the aggregate types snapshotted per the settings by replaying each
+/// aggregate's full event stream; e.g., after an aggregate's state changes shape. Snapshots of
+/// aggregate types no longer snapshotted are dropped, so snapshotting them later starts afresh.
+#[tracing::instrument(level = "info", skip(db_pool))]
+pub async fn rebuild_snapshots(
+    db_pool: &PgPool, settings: &SnapshotSettings,
+) -> Result<(), SnapshotError> {
+    rebuild_aggregate_snapshots::<LocationZone>(db_pool, settings.location_zone).await?;
+    rebuild_aggregate_snapshots::<Registrar>(db_pool, settings.registrar).await?;
+    rebuild_aggregate_snapshots::<UpdateLocations>(db_pool, settings.update_locations).await?;
+    Ok(())
+}
+
+async fn rebuild_aggregate_snapshots<A>(
+    db_pool: &PgPool, snapshot_interval: Option<usize>,
+) -> Result<(), SnapshotError>
+where
+    A: Aggregate,
+    A::Event: VersionedEvent,
+{
+    let aggregate_type = A::aggregate_type();
+    if snapshot_interval.filter(|interval| 0 < *interval).is_none() {
+        let dropped = drop_snapshots(db_pool, &aggregate_type).await?;
+        tracing::info!(%aggregate_type, %dropped, "aggregate not snapshotted - dropped snapshots");
+        return Ok(());
+    }
+
+    let store = PersistedEventStore::<PostgresEventRepository, A>::new_event_store(
+        PostgresEventRepository::new(db_pool.clone()),
+    )
+    .with_upcasters(vec![Box::new(A::Event::upcasters())]);
+
+    let aggregate_ids = find_aggregate_ids(db_pool, &aggregate_type).await?;
+    for aggregate_id in aggregate_ids.iter() {
+        let context = match store.load_aggregate(aggregate_id).await {
+            Ok(context) => context,
+            Err(error) => {
+                return Err(SnapshotError::Replay {
+                    aggregate_type,
+                    aggregate_id: aggregate_id.clone(),
+                    message: error.to_string(),
+                });
+            },
+        };
+
+        let payload = serde_json::to_value(&context.aggregate)?;
+        save_snapshot(
+            db_pool,
+            &aggregate_type,
+            aggregate_id,
+            context.current_sequence,
+            payload,
+        )
+        .await?;
+    }
+
+    tracing::info!(%aggregate_type, rebuilt=%aggregate_ids.len(), "rebuilt aggregate snapshots");
+    Ok(())
+}
+
+async fn find_aggregate_ids(
+    db_pool: &PgPool, aggregate_type: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let select_sql = sql::Select::new()
+        .select("DISTINCT aggregate_id")
+        .from("events")
+        .where_clause("aggregate_type = $1")
+        .to_string();
+
+    let rows: Vec<(String,)> =
+        sqlx::query_as(&select_sql).bind(aggregate_type).fetch_all(db_pool).await?;
+    Ok(rows.into_iter().map(|(aggregate_id,)| aggregate_id).collect())
+}
+
+/// Saves the aggregate's snapshot, advancing the snapshot version so a command concurrently
+/// committing a snapshot from the prior version fails its optimistic lock and is retried.
+async fn save_snapshot(
+    db_pool: &PgPool, aggregate_type: &str, aggregate_id: &str, last_sequence: usize,
+    payload: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    let insert_sql = sql::Insert::new()
+        .insert_into(&format!(
+            "{SNAPSHOT_TABLE} (aggregate_type, aggregate_id, last_sequence, current_snapshot, \
+             payload)"
+        ))
+        .values("($1, $2, $3, 1, $4)")
+        .on_conflict(&format!(
+            "(aggregate_type, aggregate_id) DO UPDATE SET last_sequence = EXCLUDED.last_sequence, \
+             current_snapshot = {SNAPSHOT_TABLE}.current_snapshot + 1, payload = \
+             EXCLUDED.payload, timestamp = CURRENT_TIMESTAMP"
+        ))
+        .to_string();
+
+    sqlx::query(&insert_sql)
+        .bind(aggregate_type)
+        .bind(aggregate_id)
+        .bind(i64::try_from(last_sequence).unwrap_or(i64::MAX))
+        .bind(Json(payload))
+        .execute(db_pool)
+        .await?;
+    Ok(())
+}
+
+async fn drop_snapshots(db_pool: &PgPool, aggregate_type: &str) -> Result<u64, sqlx::Error> {
+    let delete_sql = sql::Delete::new()
+        .delete_from(SNAPSHOT_TABLE)
+        .where_clause("aggregate_type = $1")
+        .to_string();
+
+    let outcome = sqlx::query(&delete_sql).bind(aggregate_type).execute(db_pool).await?;
+    Ok(outcome.rows_affected())
+}
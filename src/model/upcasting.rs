@@ -0,0 +1,189 @@
+use cqrs_es::persist::{EventUpcaster, SerializedEvent};
+use cqrs_es::DomainEvent;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Transforms an event payload from one version into the next.
+pub type UpcastFn = fn(Value) -> Value;
+
+/// Domain events whose stored payloads from past versions are upcast before deserialization.
+pub trait VersionedEvent: DomainEvent {
+    fn upcasters() -> EventUpcasterRegistry;
+}
+
+#[derive(Clone)]
+struct EventUpcast {
+    event_type: String,
+    event_version: String,
+    upcast: UpcastFn,
+}
+
+/// Upcasters of past event payloads keyed by `(event_type, event_version)`. Each upcast
+/// transforms a payload into its next version, possibly as another event type; a stored event is
+/// upcast from version to version until no upcaster remains for its type and version.
+#[derive(Clone, Default)]
+pub struct EventUpcasterRegistry {
+    upcasts: HashMap<(String, String), EventUpcast>,
+}
+
+impl fmt::Debug for EventUpcasterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventUpcasterRegistry")
+            .field("upcasts", &self.upcasts.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl EventUpcasterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the upcast of the event type's payload at the version into the version of the
+    /// target event type.
+    pub fn with_upcast(
+        mut self, (event_type, event_version): (&str, &str), (to_type, to_version): (&str, &str),
+        upcast: UpcastFn,
+    ) -> Self {
+        let upcast = EventUpcast {
+            event_type: to_type.to_string(),
+            event_version: to_version.to_string(),
+            upcast,
+        };
+        self.upcasts
+            .insert((event_type.to_string(), event_version.to_string()), upcast);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upcasts.is_empty()
+    }
+}
+
+impl EventUpcaster for EventUpcasterRegistry {
+    fn can_upcast(&self, event_type: &str, event_version: &str) -> bool {
+        self.upcasts
+            .contains_key(&(event_type.to_string(), event_version.to_string()))
+    }
+
+    fn upcast(&self, mut event: SerializedEvent) -> SerializedEvent {
+        // bounded by the number of upcasts to guard against a cycle of registrations
+        for _ in 0..self.upcasts.len() {
+            let key = (event.event_type.clone(), event.event_version.clone());
+            let step = match self.upcasts.get(&key) {
+                Some(step) => step,
+                None => break,
+            };
+
+            event.payload = (step.upcast)(event.payload);
+            event.event_type = step.event_type.clone();
+            event.event_version = step.event_version.clone();
+        }
+
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::registrar::RegistrarEvent;
+    use crate::model::update::{LocationUpdatedSteps, UpdateLocationsEvent};
+    use crate::model::zone::LocationZoneEvent;
+    use crate::model::{LocationZoneCode, LocationZoneType};
+    use claim::{assert_matches, assert_ok};
+    use pretty_assertions::assert_eq;
+    use serde::de::DeserializeOwned;
+    use serde::Deserialize;
+
+    /// Event as stored in the events table, golden from a past version.
+    #[derive(Debug, Deserialize)]
+    struct StoredEvent {
+        event_type: String,
+        event_version: String,
+        payload: Value,
+    }
+
+    fn replay_fixture<E: VersionedEvent + DeserializeOwned>(fixture: &str) -> Vec<E> {
+        let stored: Vec<StoredEvent> = assert_ok!(serde_json::from_str(fixture));
+        let registry = E::upcasters();
+
+        stored
+            .into_iter()
+            .enumerate()
+            .map(|(sequence, stored)| {
+                let event = SerializedEvent {
+                    aggregate_id: "fixture".to_string(),
+                    sequence: sequence + 1,
+                    aggregate_type: "fixture".to_string(),
+                    event_type: stored.event_type,
+                    event_version: stored.event_version,
+                    payload: stored.payload,
+                    metadata: Value::default(),
+                };
+
+                let event = if registry.can_upcast(&event.event_type, &event.event_version) {
+                    registry.upcast(event)
+                } else {
+                    event
+                };
+
+                let replayed: E = assert_ok!(serde_json::from_value(event.payload.clone()));
+                assert_eq!(replayed.event_type(), event.event_type);
+                assert_eq!(replayed.event_version(), event.event_version);
+                replayed
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_location_zone_events_v1_0() {
+        let events: Vec<LocationZoneEvent> = replay_fixture(include_str!(
+            "../../tests/fixtures/events/location_zone_v1.0.json"
+        ));
+        assert_eq!(events.len(), 5);
+        assert_matches!(
+            &events[3],
+            LocationZoneEvent::AlertActivated(alert) if alert.id.is_empty()
+        );
+        assert_eq!(events[4], LocationZoneEvent::AlertDeactivated);
+    }
+
+    #[test]
+    fn test_replay_registrar_events_v1_0() {
+        let events: Vec<RegistrarEvent> = replay_fixture(include_str!(
+            "../../tests/fixtures/events/registrar_v1.0.json"
+        ));
+        assert_eq!(
+            events,
+            vec![
+                RegistrarEvent::ZoneAdded(
+                    LocationZoneCode::new("WAZ558"),
+                    LocationZoneType::Forecast
+                ),
+                RegistrarEvent::ZoneAdded(
+                    LocationZoneCode::new("WAC033"),
+                    LocationZoneType::County
+                ),
+                RegistrarEvent::ForecastZoneForgotten(LocationZoneCode::new("WAC033")),
+                RegistrarEvent::AllForecastZonesForgotten,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_update_locations_events_v1_0() {
+        let events: Vec<UpdateLocationsEvent> = replay_fixture(include_str!(
+            "../../tests/fixtures/events/update_locations_v1.0.json"
+        ));
+        assert_eq!(events.len(), 3);
+        assert_matches!(
+            &events[0],
+            UpdateLocationsEvent::StartedSteps(_, zones, steps)
+                if zones == &[LocationZoneCode::new("WAZ558")]
+                    && *steps == LocationUpdatedSteps::all()
+        );
+        assert_eq!(events[2], UpdateLocationsEvent::Completed);
+    }
+}
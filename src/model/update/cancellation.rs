@@ -0,0 +1,105 @@
+use super::{UpdateLocations, UpdateLocationsEvent};
+use crate::model::{CancelledCorrelations, SubscribeCommand};
+use async_trait::async_trait;
+use cqrs_es::{EventEnvelope, Query};
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Once an update's cancellation is committed, drops the zone commands the update has yet to
+/// relay and unsubscribes the update from its zones' events. The update's correlation is
+/// forgotten after the retention, by when the update's commands in flight have been dropped.
+pub struct UpdateCancellationQuery {
+    cancelled: CancelledCorrelations,
+    subscriber_tx: mpsc::Sender<SubscribeCommand>,
+    retention: Duration,
+}
+
+impl fmt::Debug for UpdateCancellationQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateCancellationQuery")
+            .field("retention", &self.retention)
+            .finish()
+    }
+}
+
+impl UpdateCancellationQuery {
+    pub fn new(
+        cancelled: CancelledCorrelations, subscriber_tx: mpsc::Sender<SubscribeCommand>,
+        retention: Duration,
+    ) -> Self {
+        Self { cancelled, subscriber_tx, retention }
+    }
+
+    async fn do_cancel(&self, update_id: &str) {
+        self.cancelled.cancel(update_id).await;
+
+        let cancelled = self.cancelled.clone();
+        let correlation = update_id.to_string();
+        let retention = self.retention;
+        tokio::spawn(async move {
+            tokio::time::sleep(retention).await;
+            cancelled.forget(&correlation).await;
+        });
+
+        let command = SubscribeCommand::Remove { subscriber_id: update_id.to_string() };
+        if let Err(error) = self.subscriber_tx.send(command).await {
+            tracing::error!(
+                ?error,
+                "location broadcast unsubscription failed for cancelled update saga: {update_id}"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Query<UpdateLocations> for UpdateCancellationQuery {
+    async fn dispatch(&self, update_id: &str, events: &[EventEnvelope<UpdateLocations>]) {
+        for event in events {
+            if let UpdateLocationsEvent::Cancelled(_) = &event.payload {
+                self.do_cancel(update_id).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::update::UpdateCancellation;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_cancelled_update_cancels_its_correlation_and_unsubscribes() {
+        let cancelled = CancelledCorrelations::default();
+        let (subscriber_tx, mut subscriber_rx) = mpsc::channel(8);
+        let query = UpdateCancellationQuery::new(
+            cancelled.clone(),
+            subscriber_tx,
+            Duration::from_secs(300),
+        );
+
+        let cancellation = UpdateCancellation { cancelled_by: "otis".to_string(), reason: None };
+        query
+            .dispatch(
+                "saga-1",
+                &[EventEnvelope {
+                    aggregate_id: "saga-1".to_string(),
+                    sequence: 2,
+                    payload: UpdateLocationsEvent::Cancelled(cancellation),
+                    metadata: HashMap::new(),
+                }],
+            )
+            .await;
+
+        assert!(cancelled.is_cancelled("saga-1").await);
+        assert!(!cancelled.is_cancelled("saga-2").await);
+        assert!(matches!(
+            subscriber_rx.recv().await,
+            Some(SubscribeCommand::Remove { subscriber_id }) if subscriber_id == "saga-1"
+        ));
+
+        cancelled.forget("saga-1").await;
+        assert!(!cancelled.is_cancelled("saga-1").await);
+    }
+}
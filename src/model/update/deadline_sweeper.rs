@@ -0,0 +1,94 @@
+use super::{UpdateLocationsCommand, UpdateLocationsSaga, UPDATE_LOCATIONS_QUERY_VIEW};
+use crate::model;
+use crate::settings::UpdateDeadlineSettings;
+use sql_query_builder as sql;
+use sqlx::PgPool;
+use std::fmt;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Periodically times out update sagas still active past their deadline; e.g., when a zone's
+/// command failed or its event was dropped, so the saga never heard the zone finish. Deadlines
+/// are found from the saga views, so they survive a restart. Active sagas started before
+/// deadlines were set are swept as well, so they are given a deadline to time out by.
+pub struct UpdateDeadlineSweeper {
+    interval: Duration,
+    update_agg: UpdateLocationsSaga,
+    db_pool: PgPool,
+}
+
+impl fmt::Debug for UpdateDeadlineSweeper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateDeadlineSweeper")
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl UpdateDeadlineSweeper {
+    pub fn new(
+        settings: &UpdateDeadlineSettings, update_agg: UpdateLocationsSaga, db_pool: PgPool,
+    ) -> Self {
+        Self {
+            interval: settings.check_interval,
+            update_agg,
+            db_pool,
+        }
+    }
+
+    pub fn run(self) -> JoinHandle<()> {
+        tokio::spawn(async move { self.do_run().await })
+    }
+
+    async fn do_run(self) {
+        let mut ticks = tokio::time::interval(self.interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticks.tick().await;
+            self.do_sweep().await;
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn do_sweep(&self) {
+        let sagas = match self.find_sagas_past_deadline().await {
+            Ok(sagas) => sagas,
+            Err(error) => {
+                tracing::error!(?error, "failed to find update sagas past their deadline");
+                return;
+            },
+        };
+
+        for saga_id in sagas {
+            tracing::info!(%saga_id, "update saga past or without its deadline - noting deadline");
+            let outcome = self
+                .update_agg
+                .execute_with_metadata(
+                    &saga_id,
+                    UpdateLocationsCommand::NoteDeadlinePassed,
+                    model::occurrence_metadata(),
+                )
+                .await;
+            if let Err(error) = outcome {
+                tracing::error!(?error, %saga_id, "failed to time out update saga");
+            }
+        }
+    }
+
+    /// Finds the active sagas past their deadline or without one.
+    async fn find_sagas_past_deadline(&self) -> Result<Vec<String>, sqlx::Error> {
+        let select_sql = sql::Select::new()
+            .select("view_id")
+            .from(UPDATE_LOCATIONS_QUERY_VIEW)
+            .where_clause("payload -> 'state' -> 'Active' IS NOT NULL")
+            .where_clause(
+                "COALESCE((payload -> 'state' -> 'Active' ->> 'deadline')::timestamptz <= now(), \
+                 true)",
+            )
+            .to_string();
+
+        let rows: Vec<(String,)> = sqlx::query_as(&select_sql).fetch_all(&self.db_pool).await?;
+        Ok(rows.into_iter().map(|(view_id,)| view_id).collect())
+    }
+}
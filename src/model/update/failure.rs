@@ -0,0 +1,155 @@
+use crate::model::update::LocationUpdatedStep;
+use crate::model::zone::LocationZoneError;
+use crate::services::noaa::NoaaWeatherError;
+use cqrs_es::AggregateError;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use utoipa::ToSchema;
+
+/// Kind of error that failed a zone's update step.
+#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateFailureKind {
+    /// The step's command could not be dispatched to the zone.
+    Dispatch,
+
+    /// The weather service responded with an error status.
+    UpstreamStatus,
+
+    /// The weather service could not be reached or did not respond in time.
+    UpstreamUnavailable,
+
+    /// The weather service's response could not be understood.
+    UpstreamResponse,
+
+    /// The zone rejected the step's command.
+    Rejected,
+
+    /// The zone's events could not be loaded or committed.
+    Persistence,
+}
+
+impl From<&NoaaWeatherError> for UpdateFailureKind {
+    fn from(error: &NoaaWeatherError) -> Self {
+        use NoaaWeatherError as Error;
+
+        match error {
+            Error::HttpStatus { .. } => Self::UpstreamStatus,
+            Error::HttpRequest(_) | Error::HttpMiddleware(_) if error.http_status().is_some() => {
+                Self::UpstreamStatus
+            },
+            Error::HttpRequest(_) | Error::HttpMiddleware(_) => Self::UpstreamUnavailable,
+            Error::GeoJson(_) | Error::Weather(_) => Self::UpstreamResponse,
+            Error::NotABaseUrl(_) => Self::Rejected,
+        }
+    }
+}
+
+/// Why a zone's update step failed.
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+pub struct LocationUpdateFailure {
+    pub step: LocationUpdatedStep,
+    pub kind: UpdateFailureKind,
+
+    /// HTTP status of the weather service's response, if the service responded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+
+    pub message: String,
+}
+
+impl LocationUpdateFailure {
+    pub fn new(
+        step: LocationUpdatedStep, kind: UpdateFailureKind, message: impl Into<String>,
+    ) -> Self {
+        Self {
+            step,
+            kind,
+            http_status: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn from_noaa_error(step: LocationUpdatedStep, error: &NoaaWeatherError) -> Self {
+        Self {
+            step,
+            kind: error.into(),
+            http_status: error.http_status().map(|status| status.as_u16()),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn from_zone_error(
+        step: LocationUpdatedStep, error: &AggregateError<LocationZoneError>,
+    ) -> Self {
+        match error {
+            AggregateError::UserError(LocationZoneError::Noaa(error)) => {
+                Self::from_noaa_error(step, error)
+            },
+            AggregateError::UserError(error) => {
+                Self::new(step, UpdateFailureKind::Rejected, error.to_string())
+            },
+            error => Self::new(step, UpdateFailureKind::Persistence, error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_failure_from_zone_noaa_error() {
+        let error =
+            AggregateError::UserError(LocationZoneError::Noaa(NoaaWeatherError::HttpStatus {
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                body: "maintenance".to_string(),
+            }));
+
+        assert_eq!(
+            LocationUpdateFailure::from_zone_error(LocationUpdatedStep::Forecast, &error),
+            LocationUpdateFailure {
+                step: LocationUpdatedStep::Forecast,
+                kind: UpdateFailureKind::UpstreamStatus,
+                http_status: Some(503),
+                message: "Weather API responded with 503 Service Unavailable: maintenance"
+                    .to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_failure_from_zone_rejected_command() {
+        let error = AggregateError::UserError(LocationZoneError::RejectedCommand(
+            "retired LocationZone cannot handle command until it is watched again: Observe"
+                .to_string(),
+        ));
+
+        assert_eq!(
+            LocationUpdateFailure::from_zone_error(LocationUpdatedStep::Observation, &error),
+            LocationUpdateFailure::new(
+                LocationUpdatedStep::Observation,
+                UpdateFailureKind::Rejected,
+                "rejected command: retired LocationZone cannot handle command until it is watched \
+                 again: Observe",
+            )
+        );
+    }
+
+    #[test]
+    fn test_failure_from_zone_persistence_error() {
+        let error = AggregateError::<LocationZoneError>::AggregateConflict;
+
+        assert_eq!(
+            LocationUpdateFailure::from_zone_error(LocationUpdatedStep::Alert, &error),
+            LocationUpdateFailure::new(
+                LocationUpdatedStep::Alert,
+                UpdateFailureKind::Persistence,
+                error.to_string(),
+            )
+        );
+    }
+}
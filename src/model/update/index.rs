@@ -0,0 +1,364 @@
+use super::{UpdateCompletionStatus, UpdateLocationsEvent};
+use crate::model::registrar::{WatchlistId, WATCHLIST_METADATA};
+use crate::model::{occurred_at, LocationZoneCode, UpdateLocations};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cqrs_es::{EventEnvelope, Query};
+use either::Right;
+use serde::{Deserialize, Serialize};
+use sql_query_builder as sql;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::fmt;
+use strum_macros::{Display, EnumString};
+use utoipa::{IntoParams, ToSchema};
+
+pub const UPDATE_INDEX_TABLE: &str = "update_index";
+
+/// Most updates served for a page, unless the request asks for fewer.
+pub const MAX_UPDATES_LIMIT: u32 = 100;
+
+/// Status of an indexed update.
+#[derive(
+    Debug, Display, EnumString, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateStatus {
+    /// The update has yet to finish.
+    Running,
+    Succeeded,
+    PartiallySucceeded,
+    Failed,
+    TimedOut,
+    Cancelled,
+
+    /// The update finished with every zone skipped.
+    Skipped,
+}
+
+impl UpdateStatus {
+    /// Status of the update finished by the event, if the event finishes the update.
+    pub fn finished_by(event: &UpdateLocationsEvent) -> Option<Self> {
+        use UpdateLocationsEvent as Evt;
+
+        match event {
+            Evt::Completed => Some(Self::Succeeded),
+            Evt::PartiallySucceeded { .. } => Some(Self::PartiallySucceeded),
+            Evt::Failed => Some(Self::Failed),
+            Evt::TimedOut(_) => Some(Self::TimedOut),
+            Evt::Cancelled(_) => Some(Self::Cancelled),
+            Evt::Skipped => Some(Self::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// Search of the indexed updates, most recently started first.
+#[derive(Debug, Default, Clone, PartialEq, Eq, IntoParams, Serialize, Deserialize)]
+#[into_params(parameter_in = Query)]
+pub struct UpdateIndexParams {
+    /// Status of the updates; e.g., `failed` or `running`.
+    pub status: Option<UpdateStatus>,
+
+    /// Earliest update start time, inclusive.
+    pub since: Option<DateTime<Utc>>,
+
+    /// Latest update start time, inclusive; e.g., to find updates still running long after they
+    /// started.
+    pub until: Option<DateTime<Utc>>,
+
+    /// Zone code the updates must include.
+    pub zone: Option<String>,
+
+    /// Most updates to return, from 1 up to 100.
+    pub limit: Option<u32>,
+
+    /// Number of matching updates to skip; the `next_offset` of the previous page.
+    pub offset: Option<u32>,
+}
+
+impl UpdateIndexParams {
+    fn limit(&self) -> u32 {
+        self.limit.unwrap_or(MAX_UPDATES_LIMIT).clamp(1, MAX_UPDATES_LIMIT)
+    }
+
+    fn offset(&self) -> u32 {
+        self.offset.unwrap_or_default()
+    }
+}
+
+/// Indexed summary of an update.
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateIndexEntry {
+    pub update_id: String,
+    pub started_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+
+    pub status: UpdateStatus,
+    pub zones: Vec<LocationZoneCode>,
+    pub zone_count: u32,
+
+    /// Number of zones that failed the update, including those timed out.
+    pub failure_count: u32,
+
+    /// Watchlist that started the update, if started by a registrar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchlist: Option<String>,
+}
+
+/// Page of the indexed updates matching a search.
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateIndexPage {
+    pub updates: Vec<UpdateIndexEntry>,
+
+    /// Offset of the next page, if more updates match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u32>,
+}
+
+type UpdateIndexRow = (
+    String,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+    String,
+    Vec<String>,
+    i32,
+    i32,
+    Option<String>,
+);
+
+/// Searches the indexed updates, of the watchlist if given, most recently started first.
+#[tracing::instrument(level = "debug", skip(db_pool))]
+pub async fn search_updates(
+    db_pool: &PgPool, watchlist: Option<&WatchlistId>, params: &UpdateIndexParams,
+) -> Result<UpdateIndexPage, sqlx::Error> {
+    let select_sql = sql::Select::new()
+        .select(
+            "update_id, started_at, finished_at, status, zones, zone_count, failure_count, \
+             watchlist",
+        )
+        .from(UPDATE_INDEX_TABLE)
+        .where_clause("($1::text IS NULL OR status = $1)")
+        .where_clause("($2::timestamptz IS NULL OR $2 <= started_at)")
+        .where_clause("($3::timestamptz IS NULL OR started_at <= $3)")
+        .where_clause("($4::text IS NULL OR zones @> ARRAY[$4::text])")
+        .where_clause("($7::text IS NULL OR watchlist = $7)")
+        .order_by("started_at DESC, update_id")
+        .limit("$5")
+        .offset("$6")
+        .to_string();
+
+    // fetches one past the page to learn whether another page follows
+    let limit = params.limit();
+    let rows: Vec<UpdateIndexRow> = sqlx::query_as(&select_sql)
+        .bind(params.status.map(|status| status.to_string()))
+        .bind(params.since)
+        .bind(params.until)
+        .bind(params.zone.as_deref())
+        .bind(i64::from(limit) + 1)
+        .bind(i64::from(params.offset()))
+        .bind(watchlist.map(|watchlist| watchlist.as_ref()))
+        .fetch_all(db_pool)
+        .await?;
+
+    let page_size = usize::try_from(limit).unwrap_or(usize::MAX);
+    let next_offset = (page_size < rows.len()).then(|| params.offset() + limit);
+    let updates = rows.into_iter().take(page_size).filter_map(entry_of).collect();
+    Ok(UpdateIndexPage { updates, next_offset })
+}
+
+fn entry_of(row: UpdateIndexRow) -> Option<UpdateIndexEntry> {
+    let (update_id, started_at, finished_at, status, zones, zone_count, failure_count, watchlist) =
+        row;
+
+    let status = match status.parse() {
+        Ok(status) => status,
+        Err(error) => {
+            tracing::warn!(
+                ?error, %update_id, %status,
+                "unrecognized indexed update status -- skipped"
+            );
+            return None;
+        },
+    };
+
+    Some(UpdateIndexEntry {
+        update_id,
+        started_at,
+        finished_at,
+        status,
+        zones: zones.into_iter().map(LocationZoneCode::new).collect(),
+        zone_count: u32::try_from(zone_count).unwrap_or_default(),
+        failure_count: u32::try_from(failure_count).unwrap_or_default(),
+        watchlist,
+    })
+}
+
+/// Indexes every update locations saga by its start time, status, zones and number of failed
+/// zones, so updates can be found without knowing their identifiers.
+pub struct UpdateIndexQuery {
+    db_pool: PgPool,
+}
+
+impl fmt::Debug for UpdateIndexQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateIndexQuery").finish()
+    }
+}
+
+impl UpdateIndexQuery {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+
+    async fn record_started(
+        &self, update_id: &str, zones: &[LocationZoneCode], metadata: &HashMap<String, String>,
+    ) -> Result<(), sqlx::Error> {
+        let insert_sql = sql::Insert::new()
+            .insert_into(&format!(
+                "{UPDATE_INDEX_TABLE} (update_id, started_at, status, zones, zone_count, \
+                 watchlist)"
+            ))
+            .values("($1, $2, $3, $4, $5, $6)")
+            .on_conflict("(update_id) DO NOTHING")
+            .to_string();
+
+        let zone_codes: Vec<&str> = zones.iter().map(|zone| zone.as_ref()).collect();
+        sqlx::query(&insert_sql)
+            .bind(update_id)
+            .bind(occurred_at(metadata))
+            .bind(UpdateStatus::Running.to_string())
+            .bind(zone_codes)
+            .bind(i32::try_from(zones.len()).unwrap_or(i32::MAX))
+            .bind(metadata.get(WATCHLIST_METADATA))
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn record_failures(&self, update_id: &str, failures: usize) -> Result<(), sqlx::Error> {
+        let update_sql = sql::Update::new()
+            .update(UPDATE_INDEX_TABLE)
+            .set("failure_count = failure_count + $2")
+            .where_clause("update_id = $1")
+            .to_string();
+
+        sqlx::query(&update_sql)
+            .bind(update_id)
+            .bind(i32::try_from(failures).unwrap_or(i32::MAX))
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn record_finished(
+        &self, update_id: &str, status: UpdateStatus, metadata: &HashMap<String, String>,
+    ) -> Result<(), sqlx::Error> {
+        let update_sql = sql::Update::new()
+            .update(UPDATE_INDEX_TABLE)
+            .set("status = $2, finished_at = $3")
+            .where_clause("update_id = $1")
+            .to_string();
+
+        sqlx::query(&update_sql)
+            .bind(update_id)
+            .bind(status.to_string())
+            .bind(occurred_at(metadata))
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn do_index(
+        &self, update_id: &str, event: &EventEnvelope<UpdateLocations>,
+    ) -> Result<(), sqlx::Error> {
+        use UpdateLocationsEvent as Evt;
+
+        match &event.payload {
+            Evt::StartedSteps(_, zones, _) => {
+                self.record_started(update_id, zones, &event.metadata).await
+            },
+            Evt::LocationUpdated(_, Right(UpdateCompletionStatus::Failed)) => {
+                self.record_failures(update_id, 1).await
+            },
+            Evt::TimedOut(unfinished) if !unfinished.is_empty() => {
+                self.record_failures(update_id, unfinished.len()).await?;
+                self.record_finished(update_id, UpdateStatus::TimedOut, &event.metadata)
+                    .await
+            },
+            finished => match UpdateStatus::finished_by(finished) {
+                Some(status) => self.record_finished(update_id, status, &event.metadata).await,
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl Query<UpdateLocations> for UpdateIndexQuery {
+    async fn dispatch(&self, update_id: &str, events: &[EventEnvelope<UpdateLocations>]) {
+        for event in events {
+            if let Err(error) = self.do_index(update_id, event).await {
+                tracing::error!(?error, %update_id, "failed to index update");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::update::UpdateCancellation;
+    use claim::assert_ok;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_update_status_finished_by() {
+        assert_eq!(
+            UpdateStatus::finished_by(&UpdateLocationsEvent::PartiallySucceeded {
+                succeeded: 2,
+                failed: 1
+            }),
+            Some(UpdateStatus::PartiallySucceeded)
+        );
+        assert_eq!(
+            UpdateStatus::finished_by(&UpdateLocationsEvent::Cancelled(UpdateCancellation {
+                cancelled_by: "operator".to_string(),
+                reason: None,
+            })),
+            Some(UpdateStatus::Cancelled)
+        );
+        assert_eq!(
+            UpdateStatus::finished_by(&UpdateLocationsEvent::Skipped),
+            Some(UpdateStatus::Skipped)
+        );
+        assert_eq!(
+            UpdateStatus::finished_by(&UpdateLocationsEvent::LocationUpdated(
+                LocationZoneCode::new("WAZ558"),
+                Right(UpdateCompletionStatus::Failed),
+            )),
+            None
+        );
+
+        let status: UpdateStatus = assert_ok!("timed_out".parse());
+        assert_eq!(status, UpdateStatus::TimedOut);
+        assert_eq!(
+            UpdateStatus::PartiallySucceeded.to_string(),
+            "partially_succeeded"
+        );
+    }
+
+    #[test]
+    fn test_update_index_params_limit_within_bounds() {
+        let params = |limit| UpdateIndexParams { limit, ..UpdateIndexParams::default() };
+        assert_eq!(params(None).limit(), MAX_UPDATES_LIMIT);
+        assert_eq!(params(Some(0)).limit(), 1);
+        assert_eq!(params(Some(25)).limit(), 25);
+        assert_eq!(params(Some(1000)).limit(), MAX_UPDATES_LIMIT);
+    }
+}
@@ -1,27 +1,52 @@
+mod cancellation;
+mod deadline_sweeper;
 mod errors;
+mod failure;
+mod index;
 mod protocol;
 mod queries;
 mod saga;
 mod service;
 mod zone_controller;
+mod zone_failure_relay;
 
+pub use cancellation::UpdateCancellationQuery;
+pub use deadline_sweeper::UpdateDeadlineSweeper;
 pub use errors::UpdateLocationsError;
-pub use protocol::{location_event_to_command, UpdateLocationsCommand, UpdateLocationsEvent};
+pub use failure::{LocationUpdateFailure, UpdateFailureKind};
+pub use index::{
+    search_updates, UpdateIndexEntry, UpdateIndexPage, UpdateIndexParams, UpdateIndexQuery,
+    UpdateStatus, MAX_UPDATES_LIMIT, UPDATE_INDEX_TABLE,
+};
+pub use protocol::{
+    location_event_to_command, UpdateCancellation, UpdateLocationsCommand, UpdateLocationsEvent,
+};
 pub use queries::{
-    UpdateLocationsQuery, UpdateLocationsView, UpdateLocationsViewProjection,
-    UPDATE_LOCATIONS_QUERY_VIEW,
+    UpdateLocationsQuery, UpdateLocationsView, UpdateLocationsViewProjection, UpdateOutcome,
+    UpdateResult, UPDATE_LOCATIONS_QUERY_VIEW,
+};
+pub use saga::{
+    generate_id, LocationUpdatedStep, LocationUpdatedSteps, StepRetry, UpdateCompletionStatus,
+    UpdateLocations, UpdateLocationsId, UpdateLocationsSaga, UpdateLocationsState,
 };
-pub use saga::{generate_id, UpdateLocations, UpdateLocationsSaga, UpdateLocationsState};
 pub use service::UpdateLocationsServices;
 pub use zone_controller::UpdateLocationZoneController;
+pub use zone_failure_relay::ZoneFailureRelay;
 
 use crate::model;
-use crate::model::{CommandRelay, EventSubscriber, LocationZone, TracingQuery};
+use crate::model::registrar::{MonitoredZonesUpdateQuery, UpdateFinishedNotifier};
+use crate::model::snapshots::make_postgres_cqrs;
+use crate::model::{
+    CancelledCorrelations, CommandFailure, CommandRelay, EventSubscriber, LocationZone, Registrar,
+    TracingQuery,
+};
 use crate::services::noaa::NoaaWeatherServices;
+use crate::settings::UpdateRetrySettings;
 use cqrs_es::Query;
 use postgres_es::PostgresViewRepository;
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 pub async fn make_update_locations_saga<C>(
@@ -30,8 +55,12 @@ pub async fn make_update_locations_saga<C>(
         mpsc::Sender<model::CommandEnvelope<UpdateLocations>>,
         mpsc::Receiver<model::CommandEnvelope<UpdateLocations>>,
     ),
+    registrar_tx: mpsc::Sender<model::CommandEnvelope<Registrar>>,
+    location_failure_rx: mpsc::Receiver<CommandFailure<LocationZone>>,
+    cancelled: CancelledCorrelations,
     location_subscriber: &EventSubscriber<LocationZone, UpdateLocations, C>,
-    noaa: NoaaWeatherServices, db_pool: PgPool,
+    noaa: NoaaWeatherServices, update_timeout: Duration, retry: UpdateRetrySettings,
+    snapshot_interval: Option<usize>, db_pool: PgPool,
 ) -> (UpdateLocationsSaga, UpdateLocationsViewProjection)
 where
     C: FnMut(model::EventEnvelope<LocationZone>) -> Vec<UpdateLocationsCommand>
@@ -48,27 +77,45 @@ where
         tracing::error!(?error, "update locations query failed")
     }));
 
+    let monitored_zones_update_query =
+        MonitoredZonesUpdateQuery::new(db_pool.clone(), update_locations_view.clone());
+    let update_finished_notifier =
+        UpdateFinishedNotifier::new(registrar_tx, update_locations_view.clone());
+
     let update_locations_queries: Vec<Box<dyn Query<UpdateLocations>>> = vec![
         Box::<TracingQuery<UpdateLocations>>::default(),
         Box::new(update_locations_query),
+        Box::new(monitored_zones_update_query),
+        Box::new(update_finished_notifier),
+        Box::new(UpdateIndexQuery::new(db_pool.clone())),
+        Box::new(UpdateCancellationQuery::new(
+            cancelled,
+            location_subscriber.subscriber_admin_tx(),
+            update_timeout,
+        )),
         Box::new(UpdateLocationZoneController::new(
             noaa.clone(),
             location_tx,
-            update_tx,
+            update_tx.clone(),
         )),
     ];
-    let mut update_locations_services = UpdateLocationsServices::for_noaa(noaa);
+    let mut update_locations_services =
+        UpdateLocationsServices::for_noaa(noaa, update_timeout, retry);
     update_locations_services
         .with_subscriber_tx(location_subscriber.subscriber_admin_tx())
         .await;
-    let agg = Arc::new(postgres_es::postgres_cqrs(
+    let agg = Arc::new(make_postgres_cqrs(
         db_pool,
         update_locations_queries,
         update_locations_services,
+        snapshot_interval,
     ));
 
     let relay = CommandRelay::new(agg.clone(), update_rx);
     relay.run();
 
+    let failure_relay = ZoneFailureRelay::new(location_failure_rx, update_tx);
+    failure_relay.run();
+
     (agg, update_locations_view)
 }
@@ -1,7 +1,15 @@
-use crate::model::update::saga::{LocationUpdateStatus, UpdateLocationsId};
-use crate::model::{EventEnvelope, LocationZone, LocationZoneCode};
+use crate::model::update::failure::LocationUpdateFailure;
+use crate::model::update::saga::{
+    LocationUpdateStatus, LocationUpdatedSteps, StepRetry, UpdateLocationsId,
+};
+use crate::model::{
+    EventEnvelope, EventUpcasterRegistry, LocationZone, LocationZoneCode, VersionedEvent,
+};
+use chrono::{DateTime, Utc};
 use cqrs_es::DomainEvent;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use strum_macros::Display;
 use utoipa::ToSchema;
 
@@ -13,9 +21,16 @@ pub fn location_event_to_command(
 
     let zone = LocationZoneCode::new(envelope.publisher_id());
     match envelope.payload() {
-        ZoneEvent::ObservationAdded(_) => vec![C::NoteLocationObservationUpdated(zone)],
+        ZoneEvent::ObservationAdded(_) | ZoneEvent::ObservationConfirmed(_) => {
+            vec![C::NoteLocationObservationUpdated(zone)]
+        },
         ZoneEvent::ForecastUpdated(_) => vec![C::NoteLocationForecastUpdated(zone)],
-        ZoneEvent::AlertDeactivated | ZoneEvent::AlertActivated(_) => {
+        ZoneEvent::ZonePaused => vec![C::NoteLocationUpdateSkipped(zone)],
+        ZoneEvent::AlertActivated(_)
+        | ZoneEvent::AlertUpdated(_)
+        | ZoneEvent::AlertCleared(_)
+        | ZoneEvent::AlertsConfirmed
+        | ZoneEvent::AlertDeactivated => {
             vec![C::NoteLocationAlertStatusUpdated(zone)]
         },
         _ => vec![],
@@ -24,11 +39,37 @@ pub fn location_event_to_command(
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UpdateLocationsCommand {
-    UpdateLocations(UpdateLocationsId, Vec<LocationZoneCode>),
+    /// Update the requested steps of the zones.
+    UpdateLocations(
+        UpdateLocationsId,
+        Vec<LocationZoneCode>,
+        LocationUpdatedSteps,
+    ),
     NoteLocationObservationUpdated(LocationZoneCode),
     NoteLocationForecastUpdated(LocationZoneCode),
     NoteLocationAlertStatusUpdated(LocationZoneCode),
-    NoteLocationUpdateFailure(LocationZoneCode),
+
+    /// The zone's update step failed for the reason, so the step is retried until it runs out of
+    /// attempts.
+    NoteLocationUpdateFailure(LocationZoneCode, LocationUpdateFailure),
+
+    /// The zone was suspended during the update, so the zone no longer updates.
+    NoteLocationUpdateSkipped(LocationZoneCode),
+
+    /// Note the update's deadline has passed, which times out an unfinished update.
+    NoteDeadlinePassed,
+
+    /// Cancel the unfinished update, stopping the zone commands it has yet to relay.
+    Cancel(UpdateCancellation),
+}
+
+/// Who cancelled an update, and why.
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+pub struct UpdateCancellation {
+    pub cancelled_by: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 const VERSION: &str = "1.0";
@@ -36,10 +77,64 @@ const VERSION: &str = "1.0";
 #[derive(Debug, Display, Clone, PartialEq, ToSchema, Serialize, Deserialize)]
 #[strum(serialize_all = "snake_case")]
 pub enum UpdateLocationsEvent {
-    Started(UpdateLocationsId, Vec<LocationZoneCode>),
+    /// Update of the steps of the zones started. Updates started before steps were selectable,
+    /// stored as `started` events, are upcast to update every step.
+    StartedSteps(
+        UpdateLocationsId,
+        Vec<LocationZoneCode>,
+        LocationUpdatedSteps,
+    ),
     LocationUpdated(LocationZoneCode, LocationUpdateStatus),
+
+    /// The zone's update step failed an attempt, so the step is reissued after the backoff.
+    LocationStepRetried(LocationZoneCode, StepRetry),
+
+    /// The zone's update step ran out of attempts, failing the zone for the last attempt's reason.
+    /// Followed by the zone's failed status.
+    LocationFailed(LocationZoneCode, LocationUpdateFailure),
+
+    /// The update must finish by the deadline, else it times out.
+    DeadlineSet(DateTime<Utc>),
     Completed,
     Failed,
+
+    /// The update finished with some zones succeeding and others failing.
+    PartiallySucceeded {
+        succeeded: usize,
+        failed: usize,
+    },
+
+    /// The update's deadline passed, failing each zone yet to finish for the reason.
+    TimedOut(HashMap<LocationZoneCode, String>),
+
+    /// The update was cancelled before it finished.
+    Cancelled(UpdateCancellation),
+
+    /// The update finished with every zone skipped, so no zone was updated.
+    Skipped,
+}
+
+impl UpdateLocationsEvent {
+    /// The zones and steps of a started update.
+    pub fn started_zones(&self) -> Option<(&[LocationZoneCode], LocationUpdatedSteps)> {
+        match self {
+            Self::StartedSteps(_, zones, steps) => Some((zones.as_slice(), *steps)),
+            _ => None,
+        }
+    }
+
+    /// Whether the event finishes the update.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed
+                | Self::Failed
+                | Self::PartiallySucceeded { .. }
+                | Self::TimedOut(_)
+                | Self::Cancelled(_)
+                | Self::Skipped
+        )
+    }
 }
 
 impl DomainEvent for UpdateLocationsEvent {
@@ -51,3 +146,24 @@ impl DomainEvent for UpdateLocationsEvent {
         VERSION.to_string()
     }
 }
+
+impl VersionedEvent for UpdateLocationsEvent {
+    fn upcasters() -> EventUpcasterRegistry {
+        EventUpcasterRegistry::new().with_upcast(
+            ("started", "1.0"),
+            ("started_steps", VERSION),
+            upcast_started_into_steps,
+        )
+    }
+}
+
+/// Upcasts an update started before steps were selectable into an update of every step.
+fn upcast_started_into_steps(payload: Value) -> Value {
+    let mut fields = payload
+        .get("Started")
+        .and_then(|fields| fields.as_array())
+        .cloned()
+        .unwrap_or_default();
+    fields.push(json!(LocationUpdatedSteps::all()));
+    json!({ "StartedSteps": fields })
+}
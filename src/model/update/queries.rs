@@ -1,11 +1,15 @@
-use crate::model::update::saga::UpdateLocationsState;
+use crate::model::registrar::{WatchlistId, WATCHLIST_METADATA};
+use crate::model::update::failure::LocationUpdateFailure;
+use crate::model::update::saga::{StepRetry, UpdateCompletionStatus, UpdateLocationsState};
 use crate::model::update::UpdateLocationsEvent;
-use crate::model::{AggregateState, UpdateLocations};
+use crate::model::{AggregateState, LocationZoneCode, UpdateLocations};
 use cqrs_es::persist::GenericQuery;
 use cqrs_es::{EventEnvelope, View};
 use postgres_es::PostgresViewRepository;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use strum_macros::Display;
 use utoipa::ToSchema;
 
 pub const UPDATE_LOCATIONS_QUERY_VIEW: &str = "update_locations_query";
@@ -22,10 +26,66 @@ pub type UpdateLocationsQuery =
 pub struct UpdateLocationsView {
     pub state: UpdateLocationsState,
     pub history: Vec<UpdateLocationsEvent>,
+
+    /// Watchlist that started the update, if started by a registrar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchlist: Option<WatchlistId>,
+
+    /// Retries of each zone's failed update steps, in order of the failed attempts.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub retries: HashMap<LocationZoneCode, Vec<StepRetry>>,
+
+    /// Why each failed zone failed its update.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub failures: HashMap<LocationZoneCode, LocationUpdateFailure>,
+
+    /// How the update finished, once finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<UpdateOutcome>,
+}
+
+/// Overall result of a finished update.
+#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+pub enum UpdateResult {
+    Succeeded,
+    PartiallySucceeded,
+    Failed,
+    TimedOut,
+    Cancelled,
+
+    /// Every zone was skipped, so no zone was updated.
+    Skipped,
+}
+
+/// How an update finished, with the numbers of its zones that succeeded and failed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOutcome {
+    pub result: UpdateResult,
+    pub succeeded: usize,
+    pub failed: usize,
 }
 
 impl View<UpdateLocations> for UpdateLocationsView {
     fn update(&mut self, event: &EventEnvelope<UpdateLocations>) {
+        if event.payload.started_zones().is_some() {
+            self.watchlist = event.metadata.get(WATCHLIST_METADATA).map(WatchlistId::new);
+        }
+
+        match &event.payload {
+            UpdateLocationsEvent::LocationStepRetried(zone, retry) => {
+                self.retries.entry(zone.clone()).or_default().push(retry.clone());
+            },
+            UpdateLocationsEvent::LocationFailed(zone, failure) => {
+                self.failures.insert(zone.clone(), failure.clone());
+            },
+            finished => {
+                if let Some(outcome) = self.outcome_of(finished) {
+                    self.outcome = Some(outcome);
+                }
+            },
+        }
+
         let evt = event.payload.clone();
         self.history.push(evt.clone());
         if let Some(new_state) = self.state.apply(evt) {
@@ -33,3 +93,33 @@ impl View<UpdateLocations> for UpdateLocationsView {
         }
     }
 }
+
+impl UpdateLocationsView {
+    /// Outcome of the update finished by the event, counting the zones as finished before it.
+    fn outcome_of(&self, event: &UpdateLocationsEvent) -> Option<UpdateOutcome> {
+        use UpdateLocationsEvent as Evt;
+
+        let nr_finished = |status| match &self.state {
+            UpdateLocationsState::Active(active) => active.nr_zones_finished(status),
+            _ => 0,
+        };
+        let succeeded = nr_finished(UpdateCompletionStatus::Succeeded);
+        let failed = nr_finished(UpdateCompletionStatus::Failed);
+
+        let (result, succeeded, failed) = match event {
+            Evt::Completed => (UpdateResult::Succeeded, succeeded, failed),
+            Evt::Failed => (UpdateResult::Failed, succeeded, failed),
+            Evt::PartiallySucceeded { succeeded, failed } => {
+                (UpdateResult::PartiallySucceeded, *succeeded, *failed)
+            },
+            Evt::TimedOut(unfinished) => {
+                (UpdateResult::TimedOut, succeeded, failed + unfinished.len())
+            },
+            Evt::Cancelled(_) => (UpdateResult::Cancelled, succeeded, failed),
+            Evt::Skipped => (UpdateResult::Skipped, succeeded, failed),
+            _ => return None,
+        };
+
+        Some(UpdateOutcome { result, succeeded, failed })
+    }
+}
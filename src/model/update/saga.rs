@@ -1,17 +1,21 @@
 use super::errors::UpdateLocationsError;
+use super::failure::LocationUpdateFailure;
 use crate::model::update::service::UpdateLocationsServices;
 use crate::model::update::{UpdateLocationsCommand, UpdateLocationsEvent};
 use crate::model::{AggregateState, LocationZoneCode};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use cqrs_es::Aggregate;
 use either::{Either, Left, Right};
 use enumflags2::{bitflags, BitFlags};
 use once_cell::sync::Lazy;
 use postgres_es::PostgresCqrs;
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::collections::HashMap;
 use std::sync::Arc;
-use strum_macros::Display;
+use std::time::Duration;
+use strum_macros::{Display, EnumString};
 use tagid::{CuidId, Entity, Id, Label};
 use utoipa::ToSchema;
 
@@ -119,12 +123,18 @@ impl AggregateState for QuiescentLocationsUpdate {
         use UpdateLocationsEvent as Evt;
 
         match command {
-            Cmd::UpdateLocations(aggregate_id, zones) if !zones.is_empty() => {
+            Cmd::UpdateLocations(aggregate_id, zones, steps)
+                if !zones.is_empty() && !steps.is_empty() =>
+            {
                 tracing::debug!("DMR: Saga[{aggregate_id:?} starting to update zones: {zones:?}");
                 services
                     .add_subscriber(aggregate_id.id.to_string(), zones.as_slice())
                     .await;
-                Ok(vec![Evt::Started(aggregate_id, zones)])
+                let mut events = vec![Evt::StartedSteps(aggregate_id, zones, steps)];
+                if let Some(deadline) = services.update_deadline() {
+                    events.push(Evt::DeadlineSet(deadline));
+                }
+                Ok(events)
             },
 
             cmd => Err(Self::Error::RejectedCommand(format!(
@@ -138,14 +148,9 @@ impl AggregateState for QuiescentLocationsUpdate {
         use UpdateLocationsEvent as Evt;
 
         match event {
-            Evt::Started(aggregate_id, zones) => {
-                let location_statuses =
-                    zones.into_iter().map(|z| (z, *DEFAULT_LOCATION_UPDATE_STATUS)).collect();
-                Some(UpdateLocationsState::Active(ActiveLocationsUpdate {
-                    aggregate_id,
-                    location_statuses,
-                }))
-            },
+            Evt::StartedSteps(aggregate_id, zones, steps) => Some(UpdateLocationsState::Active(
+                ActiveLocationsUpdate::new(aggregate_id, zones, steps),
+            )),
 
             event => {
                 tracing::warn!(
@@ -162,17 +167,42 @@ pub type LocationUpdatedSteps = BitFlags<LocationUpdatedStep>;
 
 #[bitflags]
 #[repr(u8)]
-#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[derive(
+    Debug, Display, EnumString, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize,
+)]
+#[strum(ascii_case_insensitive)]
 pub enum LocationUpdatedStep {
     Observation = 0b0001,
     Forecast = 0b0010,
     Alert = 0b0100,
 }
 
-#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+#[derive(
+    Debug, Display, EnumString, Copy, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize,
+)]
 pub enum UpdateCompletionStatus {
     Succeeded,
     Failed,
+
+    /// The zone was suspended, or its update cancelled, before it finished updating.
+    Skipped,
+}
+
+/// Retry of a zone's update step after a failed attempt of the step.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, Serialize, Deserialize)]
+pub struct StepRetry {
+    /// Why the attempt of the step failed.
+    pub failure: LocationUpdateFailure,
+
+    /// Attempt of the step that failed, starting from 1.
+    pub failed_attempt: u32,
+
+    /// Wait before the step is reissued, in milliseconds.
+    #[serde(rename = "backoff_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[schema(value_type = u64)]
+    pub backoff: Duration,
 }
 
 pub static DEFAULT_LOCATION_UPDATE_STATUS: Lazy<LocationUpdateStatus> =
@@ -199,6 +229,18 @@ impl LocationUpdateStatusExt for LocationUpdateStatus {
 pub struct ActiveLocationsUpdate {
     pub aggregate_id: UpdateLocationsId,
     pub location_statuses: HashMap<LocationZoneCode, LocationUpdateStatus>,
+
+    /// Steps to complete for each zone.
+    #[serde(default = "LocationUpdatedSteps::all")]
+    pub steps: LocationUpdatedSteps,
+
+    /// Time by which the update must finish, else it times out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+
+    /// Retries of each zone's failed update steps.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub retries: HashMap<LocationZoneCode, Vec<StepRetry>>,
 }
 
 #[async_trait]
@@ -217,7 +259,7 @@ impl AggregateState for ActiveLocationsUpdate {
         use UpdateLocationsCommand as Cmd;
 
         match command {
-            Cmd::UpdateLocations(_, _) => {
+            Cmd::UpdateLocations(..) => {
                 // Ok(vec![Evt::Started(aggregate_id, locations)])
                 Err(UpdateLocationsError::RejectedCommand(
                     "UpdateLocations saga a already updating locations".to_string(),
@@ -232,7 +274,12 @@ impl AggregateState for ActiveLocationsUpdate {
             Cmd::NoteLocationAlertStatusUpdated(zone) => {
                 self.handle_location_update(zone, Step::Alert, services)
             },
-            Cmd::NoteLocationUpdateFailure(zone) => self.handle_location_failure(zone, services),
+            Cmd::NoteLocationUpdateFailure(zone, failure) => {
+                self.handle_location_failure(zone, failure, services)
+            },
+            Cmd::NoteLocationUpdateSkipped(zone) => Ok(self.handle_location_skipped(zone)),
+            Cmd::NoteDeadlinePassed => Ok(self.handle_deadline_passed(Utc::now(), services)),
+            Cmd::Cancel(cancellation) => Ok(vec![UpdateLocationsEvent::Cancelled(cancellation)]),
         }
     }
 
@@ -251,9 +298,28 @@ impl AggregateState for ActiveLocationsUpdate {
                 Some(Self::State::Active(new_state))
             },
 
-            Evt::Completed | Evt::Failed => Some(Self::State::Finished(FinishedLocationsUpdate)),
+            Evt::LocationStepRetried(zone, retry) => {
+                let mut new_state = self.clone();
+                new_state.retries.entry(zone).or_default().push(retry);
+                Some(Self::State::Active(new_state))
+            },
+
+            Evt::DeadlineSet(deadline) => Some(Self::State::Active(Self {
+                deadline: Some(deadline),
+                ..self.clone()
+            })),
+
+            Evt::Completed
+            | Evt::Failed
+            | Evt::PartiallySucceeded { .. }
+            | Evt::TimedOut(_)
+            | Evt::Cancelled(_)
+            | Evt::Skipped => Some(Self::State::Finished(FinishedLocationsUpdate)),
 
-            Evt::Started(_, _) => {
+            // the zone's failed status follows
+            Evt::LocationFailed(..) => None,
+
+            Evt::StartedSteps(..) => {
                 tracing::warn!(
                     ?event,
                     "unrecognized update locations saga event while active -- ignored"
@@ -265,6 +331,20 @@ impl AggregateState for ActiveLocationsUpdate {
 }
 
 impl ActiveLocationsUpdate {
+    fn new(
+        aggregate_id: UpdateLocationsId, zones: Vec<LocationZoneCode>, steps: LocationUpdatedSteps,
+    ) -> Self {
+        let location_statuses =
+            zones.into_iter().map(|z| (z, *DEFAULT_LOCATION_UPDATE_STATUS)).collect();
+        Self {
+            aggregate_id,
+            location_statuses,
+            steps,
+            deadline: None,
+            retries: HashMap::new(),
+        }
+    }
+
     #[tracing::instrument(level = "debug")]
     fn handle_location_update(
         &self, zone: LocationZoneCode, step: LocationUpdatedStep,
@@ -283,25 +363,12 @@ impl ActiveLocationsUpdate {
 
         let events = match (previous, step) {
             (None, _) => vec![],
+            (Some(_), current) if !self.steps.contains(current) => vec![],
             (Some(previous), current) if previous.contains(current) => vec![],
-            (Some(mut zone_steps), current) if self.is_only_active_zone(&zone) => {
-                zone_steps.toggle(current);
-                if zone_steps.is_all() {
-                    vec![
-                        Evt::LocationUpdated(zone, Right(UpdateCompletionStatus::Succeeded)),
-                        Evt::Completed,
-                    ]
-                } else {
-                    vec![Evt::LocationUpdated(zone, Left(zone_steps))]
-                }
-            },
             (Some(mut zone_steps), current) => {
                 zone_steps.toggle(current);
-                if zone_steps.is_all() {
-                    vec![Evt::LocationUpdated(
-                        zone,
-                        Right(UpdateCompletionStatus::Succeeded),
-                    )]
+                if zone_steps.contains(self.steps) {
+                    self.finish_zone(zone, UpdateCompletionStatus::Succeeded)
                 } else {
                     vec![Evt::LocationUpdated(zone, Left(zone_steps))]
                 }
@@ -311,9 +378,12 @@ impl ActiveLocationsUpdate {
         Ok(events)
     }
 
+    /// Retries the zone's failed update step after a backoff, failing the zone for the reason
+    /// once the step has no attempts left.
     #[tracing::instrument(level = "debug")]
     fn handle_location_failure(
-        &self, zone: LocationZoneCode, services: &UpdateLocationsServices,
+        &self, zone: LocationZoneCode, failure: LocationUpdateFailure,
+        services: &UpdateLocationsServices,
     ) -> Result<Vec<UpdateLocationsEvent>, UpdateLocationsError> {
         use UpdateLocationsEvent as Evt;
 
@@ -323,20 +393,126 @@ impl ActiveLocationsUpdate {
             .unwrap_or(&DEFAULT_LOCATION_UPDATE_STATUS);
 
         let events = match previous {
-            Left(_steps) if self.is_only_active_zone(&zone) => vec![
-                Evt::LocationUpdated(zone, Right(UpdateCompletionStatus::Failed)),
-                Evt::Failed,
-            ],
-            Left(_steps) => vec![Evt::LocationUpdated(
-                zone,
-                Right(UpdateCompletionStatus::Failed),
-            )],
+            Left(_) if !self.steps.contains(failure.step) => vec![],
+            Left(zone_steps) if zone_steps.contains(failure.step) => vec![],
+            Left(_) => {
+                let failed_attempt = self.nr_step_retries(&zone, failure.step) + 1;
+                match services.retry_backoff(failed_attempt) {
+                    Some(backoff) => vec![Evt::LocationStepRetried(
+                        zone,
+                        StepRetry { failure, failed_attempt, backoff },
+                    )],
+                    None => {
+                        let mut events = vec![Evt::LocationFailed(zone.clone(), failure)];
+                        events.extend(self.finish_zone(zone, UpdateCompletionStatus::Failed));
+                        events
+                    },
+                }
+            },
             Right(_status) => vec![],
         };
 
         Ok(events)
     }
 
+    fn nr_step_retries(&self, zone: &LocationZoneCode, step: LocationUpdatedStep) -> u32 {
+        let nr_retries = self
+            .retries
+            .get(zone)
+            .map(|retries| retries.iter().filter(|retry| retry.failure.step == step).count())
+            .unwrap_or(0);
+        u32::try_from(nr_retries).unwrap_or(u32::MAX)
+    }
+
+    /// Skips the rest of the suspended zone's update, so the zone does not hold up the saga.
+    #[tracing::instrument(level = "debug")]
+    fn handle_location_skipped(&self, zone: LocationZoneCode) -> Vec<UpdateLocationsEvent> {
+        let previous = self
+            .location_statuses
+            .get(&zone)
+            .unwrap_or(&DEFAULT_LOCATION_UPDATE_STATUS);
+
+        match previous {
+            Left(_steps) => self.finish_zone(zone, UpdateCompletionStatus::Skipped),
+            Right(_status) => vec![],
+        }
+    }
+
+    /// Finishes the zone's update with the status, and then the update itself once no other zone
+    /// remains active.
+    fn finish_zone(
+        &self, zone: LocationZoneCode, status: UpdateCompletionStatus,
+    ) -> Vec<UpdateLocationsEvent> {
+        use UpdateLocationsEvent as Evt;
+
+        if !self.is_only_active_zone(&zone) {
+            return vec![Evt::LocationUpdated(zone, Right(status))];
+        }
+
+        let mut succeeded = self.nr_zones_finished(UpdateCompletionStatus::Succeeded);
+        let mut failed = self.nr_zones_finished(UpdateCompletionStatus::Failed);
+        match status {
+            UpdateCompletionStatus::Succeeded => succeeded += 1,
+            UpdateCompletionStatus::Failed => failed += 1,
+            UpdateCompletionStatus::Skipped => {},
+        }
+
+        let outcome = match (succeeded, failed) {
+            (0, 0) => Evt::Skipped,
+            (_, 0) => Evt::Completed,
+            (0, _) => Evt::Failed,
+            (succeeded, failed) => Evt::PartiallySucceeded { succeeded, failed },
+        };
+
+        vec![Evt::LocationUpdated(zone, Right(status)), outcome]
+    }
+
+    /// Number of zones that finished their update with the status.
+    pub fn nr_zones_finished(&self, status: UpdateCompletionStatus) -> usize {
+        self.location_statuses
+            .values()
+            .filter(|zone_status| zone_status.right() == Some(status))
+            .count()
+    }
+
+    /// Times out the update once its deadline has passed, failing each zone yet to finish with
+    /// the steps it did not finish. An update started before updates had deadlines is given one
+    /// from now, so it times out in turn rather than staying active.
+    #[tracing::instrument(level = "debug")]
+    fn handle_deadline_passed(
+        &self, now: DateTime<Utc>, services: &UpdateLocationsServices,
+    ) -> Vec<UpdateLocationsEvent> {
+        let deadline = match self.deadline {
+            Some(deadline) if deadline <= now => deadline,
+            Some(_) => return vec![],
+            None => {
+                return services
+                    .update_deadline()
+                    .map(UpdateLocationsEvent::DeadlineSet)
+                    .into_iter()
+                    .collect()
+            },
+        };
+
+        let unfinished = self
+            .location_statuses
+            .iter()
+            .filter_map(|(zone, status)| {
+                status.left().map(|finished| {
+                    let missed: Vec<_> =
+                        (self.steps & !finished).iter().map(|step| step.to_string()).collect();
+                    let reason = format!(
+                        "update deadline {deadline} passed before zone finished steps: {}",
+                        missed.join(", ")
+                    );
+                    (zone.clone(), reason)
+                })
+            })
+            .collect();
+
+        vec![UpdateLocationsEvent::TimedOut(unfinished)]
+    }
+
     fn is_only_active_zone(&self, zone: &LocationZoneCode) -> bool {
         self.location_statuses
             .get(zone)
@@ -381,3 +557,215 @@ impl AggregateState for FinishedLocationsUpdate {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::update::{UpdateCancellation, UpdateFailureKind};
+    use crate::services::noaa::{HappyPathWeatherServices, NoaaWeatherServices};
+    use crate::settings::UpdateRetrySettings;
+    use chrono::TimeZone;
+
+    fn test_services() -> UpdateLocationsServices {
+        UpdateLocationsServices::for_noaa(
+            NoaaWeatherServices::HappyPath(HappyPathWeatherServices),
+            Duration::from_secs(300),
+            UpdateRetrySettings::default(),
+        )
+    }
+
+    #[test]
+    fn test_deadline_passed_times_out_unfinished_zones() {
+        let saga_id = generate_id();
+        let done = LocationZoneCode::new("WAZ558");
+        let unfinished = LocationZoneCode::new("WAC033");
+        let deadline = Utc.with_ymd_and_hms(2023, 3, 15, 12, 0, 0).unwrap();
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![
+                UpdateLocationsEvent::StartedSteps(
+                    saga_id,
+                    vec![done.clone(), unfinished.clone()],
+                    LocationUpdatedSteps::all(),
+                ),
+                UpdateLocationsEvent::DeadlineSet(deadline),
+                UpdateLocationsEvent::LocationUpdated(
+                    done,
+                    Right(UpdateCompletionStatus::Succeeded),
+                ),
+                UpdateLocationsEvent::LocationUpdated(
+                    unfinished.clone(),
+                    Left(LocationUpdatedStep::Forecast.into()),
+                ),
+            ])
+            .when(UpdateLocationsCommand::NoteDeadlinePassed)
+            .then_expect_events(vec![UpdateLocationsEvent::TimedOut(HashMap::from([(
+                unfinished,
+                format!(
+                    "update deadline {deadline} passed before zone finished steps: Observation, \
+                     Alert"
+                ),
+            )]))]);
+    }
+
+    #[test]
+    fn test_deadline_passed_sets_deadline_of_update_without_one() {
+        let events = cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![UpdateLocationsEvent::StartedSteps(
+                generate_id(),
+                vec![LocationZoneCode::new("WAZ558")],
+                LocationUpdatedSteps::all(),
+            )])
+            .when(UpdateLocationsCommand::NoteDeadlinePassed)
+            .inspect_result();
+        let events = claim::assert_ok!(events);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            UpdateLocationsEvent::DeadlineSet(deadline) if Utc::now() < deadline
+        ));
+    }
+
+    #[test]
+    fn test_failed_step_retried_until_out_of_attempts() {
+        let saga_id = generate_id();
+        let zone = LocationZoneCode::new("WAZ558");
+        let started = UpdateLocationsEvent::StartedSteps(
+            saga_id,
+            vec![zone.clone()],
+            LocationUpdatedSteps::all(),
+        );
+        let failure = LocationUpdateFailure {
+            step: LocationUpdatedStep::Forecast,
+            kind: UpdateFailureKind::UpstreamStatus,
+            http_status: Some(503),
+            message: "Weather API responded with 503 Service Unavailable".to_string(),
+        };
+        let retry = |failed_attempt, backoff_secs| {
+            UpdateLocationsEvent::LocationStepRetried(
+                zone.clone(),
+                StepRetry {
+                    failure: failure.clone(),
+                    failed_attempt,
+                    backoff: Duration::from_secs(backoff_secs),
+                },
+            )
+        };
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![started.clone(), retry(1, 2)])
+            .when(UpdateLocationsCommand::NoteLocationUpdateFailure(
+                zone.clone(),
+                failure.clone(),
+            ))
+            .then_expect_events(vec![retry(2, 4)]);
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![started, retry(1, 2), retry(2, 4)])
+            .when(UpdateLocationsCommand::NoteLocationUpdateFailure(
+                zone.clone(),
+                failure.clone(),
+            ))
+            .then_expect_events(vec![
+                UpdateLocationsEvent::LocationFailed(zone.clone(), failure.clone()),
+                UpdateLocationsEvent::LocationUpdated(
+                    zone.clone(),
+                    Right(UpdateCompletionStatus::Failed),
+                ),
+                UpdateLocationsEvent::Failed,
+            ]);
+    }
+
+    #[test]
+    fn test_mixed_zone_outcomes_partially_succeed() {
+        let failed = LocationZoneCode::new("WAZ558");
+        let succeeded = LocationZoneCode::new("WAC033");
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![
+                UpdateLocationsEvent::StartedSteps(
+                    generate_id(),
+                    vec![failed.clone(), succeeded.clone()],
+                    LocationUpdatedStep::Observation.into(),
+                ),
+                UpdateLocationsEvent::LocationUpdated(
+                    failed,
+                    Right(UpdateCompletionStatus::Failed),
+                ),
+            ])
+            .when(UpdateLocationsCommand::NoteLocationObservationUpdated(
+                succeeded.clone(),
+            ))
+            .then_expect_events(vec![
+                UpdateLocationsEvent::LocationUpdated(
+                    succeeded,
+                    Right(UpdateCompletionStatus::Succeeded),
+                ),
+                UpdateLocationsEvent::PartiallySucceeded { succeeded: 1, failed: 1 },
+            ]);
+    }
+
+    #[test]
+    fn test_every_zone_skipped_finishes_skipped() {
+        let paused = LocationZoneCode::new("WAZ558");
+        let suspended = LocationZoneCode::new("WAC033");
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![
+                UpdateLocationsEvent::StartedSteps(
+                    generate_id(),
+                    vec![paused.clone(), suspended.clone()],
+                    LocationUpdatedSteps::all(),
+                ),
+                UpdateLocationsEvent::LocationUpdated(
+                    paused,
+                    Right(UpdateCompletionStatus::Skipped),
+                ),
+            ])
+            .when(UpdateLocationsCommand::NoteLocationUpdateSkipped(
+                suspended.clone(),
+            ))
+            .then_expect_events(vec![
+                UpdateLocationsEvent::LocationUpdated(
+                    suspended,
+                    Right(UpdateCompletionStatus::Skipped),
+                ),
+                UpdateLocationsEvent::Skipped,
+            ]);
+    }
+
+    #[test]
+    fn test_cancel_finishes_unfinished_update() {
+        let zone = LocationZoneCode::new("WAZ558");
+        let cancellation = UpdateLocationsCommand::Cancel(UpdateCancellation {
+            cancelled_by: "otis".to_string(),
+            reason: Some("NOAA maintenance window".to_string()),
+        });
+        let started = UpdateLocationsEvent::StartedSteps(
+            generate_id(),
+            vec![zone.clone()],
+            LocationUpdatedSteps::all(),
+        );
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![started.clone()])
+            .when(cancellation.clone())
+            .then_expect_events(vec![UpdateLocationsEvent::Cancelled(UpdateCancellation {
+                cancelled_by: "otis".to_string(),
+                reason: Some("NOAA maintenance window".to_string()),
+            })]);
+
+        cqrs_es::test::TestFramework::<UpdateLocations>::with(test_services())
+            .given(vec![
+                started,
+                UpdateLocationsEvent::LocationUpdated(zone, Right(UpdateCompletionStatus::Failed)),
+                UpdateLocationsEvent::Failed,
+            ])
+            .when(cancellation)
+            .then_expect_error_message(
+                "rejected command: Finished UpdateLocations saga does not handle commands: \
+                 Cancel(UpdateCancellation { cancelled_by: \"otis\", reason: Some(\"NOAA \
+                 maintenance window\") })",
+            );
+    }
+}
@@ -1,32 +1,57 @@
 use crate::model::{LocationZoneCode, SubscribeCommand, WeatherAlert};
 use crate::services::noaa::{AlertApi, NoaaWeatherError, NoaaWeatherServices};
+use crate::settings::UpdateRetrySettings;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 
 #[derive(Debug, Clone)]
 pub struct UpdateLocationsServices {
     location_subscriber_tx: Arc<RwLock<Option<mpsc::Sender<SubscribeCommand>>>>,
     noaa: NoaaWeatherServices,
+    update_timeout: Duration,
+    retry: UpdateRetrySettings,
 }
 
 impl UpdateLocationsServices {
     pub fn new(
         location_subscriber_tx: mpsc::Sender<SubscribeCommand>, noaa: NoaaWeatherServices,
+        update_timeout: Duration, retry: UpdateRetrySettings,
     ) -> Self {
         Self {
             location_subscriber_tx: Arc::new(RwLock::new(Some(location_subscriber_tx))),
             noaa,
+            update_timeout,
+            retry,
         }
     }
 
-    pub fn for_noaa(noaa: NoaaWeatherServices) -> Self {
+    pub fn for_noaa(
+        noaa: NoaaWeatherServices, update_timeout: Duration, retry: UpdateRetrySettings,
+    ) -> Self {
         Self {
             location_subscriber_tx: Arc::new(RwLock::new(None)),
             noaa,
+            update_timeout,
+            retry,
         }
     }
 
+    /// Deadline of an update starting now, unless the timeout is beyond representable time.
+    pub fn update_deadline(&self) -> Option<DateTime<Utc>> {
+        chrono::Duration::from_std(self.update_timeout)
+            .ok()
+            .and_then(|timeout| Utc::now().checked_add_signed(timeout))
+    }
+
+    /// Wait before retrying a zone's update step that failed the attempt, unless the step has no
+    /// attempts left.
+    pub fn retry_backoff(&self, failed_attempt: u32) -> Option<Duration> {
+        self.retry.backoff(failed_attempt)
+    }
+
     pub async fn with_subscriber_tx(&mut self, subscriber_tx: mpsc::Sender<SubscribeCommand>) {
         *self.location_subscriber_tx.write().await = Some(subscriber_tx);
     }
@@ -1,14 +1,18 @@
 use super::UpdateLocations;
-use crate::model::update::{UpdateLocationsCommand, UpdateLocationsEvent as E};
+use crate::model::update::{
+    LocationUpdateFailure, LocationUpdatedStep as Step, StepRetry, UpdateFailureKind,
+    UpdateLocationsCommand, UpdateLocationsEvent,
+};
 use crate::model::zone::LocationZoneCommand;
 use crate::model::{self, LocationZone, LocationZoneCode, WeatherAlert};
-use crate::services::noaa::{AlertApi, NoaaWeatherServices};
+use crate::services::noaa::{AlertApi, NoaaWeatherError, NoaaWeatherServices};
 use async_trait::async_trait;
 use cqrs_es::Query;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
-use tokio::{sync::mpsc, task};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task;
 
 pub struct UpdateLocationZoneController {
     inner: Arc<UpdateLocationZoneControllerRef>,
@@ -20,7 +24,12 @@ impl UpdateLocationZoneController {
         update_tx: mpsc::Sender<model::CommandEnvelope<UpdateLocations>>,
     ) -> Self {
         Self {
-            inner: Arc::new(UpdateLocationZoneControllerRef { noaa, location_tx, update_tx }),
+            inner: Arc::new(UpdateLocationZoneControllerRef {
+                noaa,
+                location_tx,
+                update_tx,
+                alert_retries: Mutex::new(HashMap::new()),
+            }),
         }
     }
 }
@@ -30,6 +39,10 @@ struct UpdateLocationZoneControllerRef {
     pub noaa: NoaaWeatherServices,
     pub location_tx: mpsc::Sender<model::CommandEnvelope<LocationZone>>,
     pub update_tx: mpsc::Sender<model::CommandEnvelope<UpdateLocations>>,
+
+    /// Zones awaiting each saga's retry of the shared alerts fetch, so the zones failed by one
+    /// fetch are retried by one fetch.
+    alert_retries: Mutex<HashMap<String, Vec<LocationZoneCode>>>,
 }
 
 impl fmt::Debug for UpdateLocationZoneController {
@@ -43,33 +56,49 @@ impl Query<UpdateLocations> for UpdateLocationZoneController {
     async fn dispatch(
         &self, update_saga_id: &str, events: &[cqrs_es::EventEnvelope<UpdateLocations>],
     ) {
-        let metadata =
-            maplit::hashmap! { "correlation".to_string() => update_saga_id.to_string(), };
+        let metadata = maplit::hashmap! {
+            model::CORRELATION_METADATA.to_string() => update_saga_id.to_string(),
+        };
 
         for event in events {
-            if let E::Started(_, zones) = &event.payload {
+            if let Some((zones, steps)) = event.payload.started_zones() {
                 let saga_id = update_saga_id.to_string();
-                let zones = zones.clone();
+                let zones = zones.to_vec();
                 let metadata = metadata.clone();
 
-                self.inner.clone().do_spawn_update_observations(
-                    saga_id.as_str(),
-                    zones.as_slice(),
-                    &metadata,
-                );
+                if steps.contains(Step::Observation) {
+                    self.inner.clone().do_spawn_update_observations(
+                        saga_id.as_str(),
+                        zones.as_slice(),
+                        &metadata,
+                    );
+                }
 
-                self.inner.clone().do_spawn_update_forecasts(
-                    saga_id.as_str(),
-                    zones.as_slice(),
-                    &metadata,
-                );
+                if steps.contains(Step::Forecast) {
+                    self.inner.clone().do_spawn_update_forecasts(
+                        saga_id.as_str(),
+                        zones.as_slice(),
+                        &metadata,
+                    );
+                }
 
-                let inner_ref = self.inner.clone();
-                tokio::spawn(async move {
-                    inner_ref
-                        .do_spawn_update_alerts(saga_id.as_str(), zones.as_slice(), &metadata)
-                        .await;
-                });
+                if steps.contains(Step::Alert) {
+                    let inner_ref = self.inner.clone();
+                    tokio::spawn(async move {
+                        inner_ref
+                            .do_spawn_update_alerts(saga_id.as_str(), zones.as_slice(), &metadata)
+                            .await;
+                    });
+                }
+            }
+
+            if let UpdateLocationsEvent::LocationStepRetried(zone, retry) = &event.payload {
+                self.inner.clone().do_spawn_retry_step(
+                    update_saga_id,
+                    zone.clone(),
+                    retry.clone(),
+                    metadata.clone(),
+                );
             }
         }
     }
@@ -114,44 +143,80 @@ impl UpdateLocationZoneControllerRef {
         self: Arc<Self>, update_saga_id: &str, zones: &[LocationZoneCode],
         metadata: &HashMap<String, String>,
     ) {
-        let update_zones: HashSet<_> = zones.iter().cloned().collect();
-        let mut alerted_zones = HashSet::with_capacity(update_zones.len());
+        let alerts = match self.do_get_alerts().await {
+            Ok(alerts) => alerts,
+            Err(error) => {
+                // leave the zones' alerts as last noted rather than clearing them
+                let failure = LocationUpdateFailure::from_noaa_error(Step::Alert, &error);
+                for zone in zones {
+                    self.do_note_failure(update_saga_id, zone, failure.clone(), metadata.clone())
+                        .await;
+                }
+                return;
+            },
+        };
 
-        let alerts = self.do_get_alerts().await;
+        let mut zone_alerts: HashMap<_, Vec<WeatherAlert>> =
+            zones.iter().map(|zone| (zone.clone(), Vec::new())).collect();
         let nr_alerts = alerts.len();
         for alert in alerts {
-            let update_affected = alert.affected_zones.iter().filter(|z| update_zones.contains(z));
-
-            for affected in update_affected.cloned() {
-                alerted_zones.insert(affected.clone());
-                let self_ref = self.clone();
-                let saga_id = update_saga_id.to_string();
-                let alert = alert.clone();
-                let metadata = metadata.clone();
-                task::spawn(async move {
-                    tracing::debug!(?alert, "spawning alert update on {affected} zone..");
-                    self_ref.do_update_zone_alert(&saga_id, affected, alert, metadata).await;
-                });
+            for affected in alert.affected_zones.iter() {
+                if let Some(affected_alerts) = zone_alerts.get_mut(affected) {
+                    affected_alerts.push(alert.clone());
+                }
             }
         }
 
-        let unaffected: Vec<_> = update_zones.difference(&alerted_zones).cloned().collect();
-        tracing::info!(?alerted_zones, ?unaffected, %nr_alerts, "DMR: finishing alerting with unaffected notes..");
-        for zone in unaffected {
+        tracing::info!(%nr_alerts, "noting active alerts of {} zones", zone_alerts.len());
+        for (zone, alerts) in zone_alerts {
+            let self_ref = self.clone();
+            let saga_id = update_saga_id.to_string();
             let metadata = metadata.clone();
-            let command = model::CommandEnvelope::new_with_metadata(
-                update_saga_id,
-                UpdateLocationsCommand::NoteLocationAlertStatusUpdated(zone.clone()),
-                metadata.clone(),
+            task::spawn(async move {
+                tracing::debug!(?alerts, "spawning alert update on {zone} zone..");
+                self_ref.do_update_zone_alerts(&saga_id, zone, alerts, metadata).await;
+            });
+        }
+    }
+
+    /// Reissues the zone's failed update step once the retry's backoff has passed. Alert retries
+    /// of the saga's zones pending together share one alerts fetch.
+    #[tracing::instrument(level = "trace", skip())]
+    fn do_spawn_retry_step(
+        self: Arc<Self>, update_saga_id: &str, zone: LocationZoneCode, retry: StepRetry,
+        metadata: HashMap<String, String>,
+    ) {
+        let saga_id = update_saga_id.to_string();
+        task::spawn(async move {
+            if retry.failure.step == Step::Alert {
+                let mut alert_retries = self.alert_retries.lock().await;
+                let pending = alert_retries.entry(saga_id.clone()).or_default();
+                pending.push(zone.clone());
+                if 1 < pending.len() {
+                    // the saga's alerts fetch is already scheduled for retry
+                    return;
+                }
+            }
+
+            tokio::time::sleep(retry.backoff).await;
+            tracing::info!(
+                failed_attempt=%retry.failed_attempt, failure=?retry.failure,
+                "retrying {step} update on {zone} zone..",
+                step = retry.failure.step
             );
-            let outcome = self.update_tx.send(command.clone()).await;
-            if let Err(error) = outcome {
-                tracing::error!(
-                    ?error,
-                    "failed to update saga on zone unaffected by alert status: {command:?}"
-                );
+
+            match retry.failure.step {
+                Step::Observation => {
+                    self.do_update_zone_observation(&saga_id, &zone, metadata).await
+                },
+                Step::Forecast => self.do_update_zone_forecast(&saga_id, &zone, metadata).await,
+                Step::Alert => {
+                    let zones =
+                        self.alert_retries.lock().await.remove(&saga_id).unwrap_or_default();
+                    self.do_spawn_update_alerts(&saga_id, &zones, &metadata).await
+                },
             }
-        }
+        });
     }
 
     #[tracing::instrument(level = "trace", skip())]
@@ -164,7 +229,7 @@ impl UpdateLocationZoneControllerRef {
             metadata,
         );
 
-        self.do_send_command(update_saga_id, command).await;
+        self.do_send_command(update_saga_id, Step::Observation, command).await;
     }
 
     #[tracing::instrument(level = "trace", skip())]
@@ -177,37 +242,39 @@ impl UpdateLocationZoneControllerRef {
             metadata,
         );
 
-        self.do_send_command(update_saga_id, command).await
+        self.do_send_command(update_saga_id, Step::Forecast, command).await;
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
-    async fn do_get_alerts(&self) -> Vec<WeatherAlert> {
-        match self.noaa.active_alerts().await {
-            Ok(alerts) => alerts,
-            Err(error) => {
-                tracing::error!(?error, "failed to pull weather alerts from NOAA.");
-                vec![]
-            },
+    async fn do_get_alerts(&self) -> Result<Vec<WeatherAlert>, NoaaWeatherError> {
+        let outcome = self.noaa.active_alerts().await;
+        if let Err(ref error) = outcome {
+            tracing::error!(?error, "failed to pull weather alerts from NOAA.");
         }
+        outcome
     }
 
+    /// Notes the zone's active alerts. The update saga notes the zone's alert step from the
+    /// alert events the zone records, or from the command's failure.
     #[tracing::instrument(level = "trace", skip())]
-    async fn do_update_zone_alert(
-        &self, update_saga_id: &str, zone: LocationZoneCode, alert: WeatherAlert,
+    async fn do_update_zone_alerts(
+        &self, update_saga_id: &str, zone: LocationZoneCode, alerts: Vec<WeatherAlert>,
         metadata: HashMap<String, String>,
     ) {
         let command = model::CommandEnvelope::new_with_metadata(
             zone,
-            LocationZoneCommand::NoteAlert(Some(alert)),
+            LocationZoneCommand::NoteAlerts(alerts),
             metadata,
         );
 
-        self.do_send_command(update_saga_id, command).await
+        self.do_send_command(update_saga_id, Step::Alert, command).await;
     }
 
+    /// Sends the command for the zone's update step, noting the step's failure with the update
+    /// saga if the command cannot be sent.
     #[tracing::instrument(level = "trace", skip())]
     async fn do_send_command(
-        &self, update_saga_id: &str, command: model::CommandEnvelope<LocationZone>,
+        &self, update_saga_id: &str, step: Step, command: model::CommandEnvelope<LocationZone>,
     ) {
         let zone = LocationZoneCode::new(command.target_id());
         let metadata = command.metadata().clone();
@@ -217,25 +284,37 @@ impl UpdateLocationZoneControllerRef {
             ?command,
             "sending command to location aggregate channel"
         );
-        if send_outcome.is_err() {
-            let command = model::CommandEnvelope::new_with_metadata(
-                update_saga_id,
-                UpdateLocationsCommand::NoteLocationUpdateFailure(zone.clone()),
-                metadata,
+        if let Err(ref error) = send_outcome {
+            let failure = LocationUpdateFailure::new(
+                step,
+                UpdateFailureKind::Dispatch,
+                format!("failed to send {step} command to zone: {error}"),
             );
+            self.do_note_failure(update_saga_id, &zone, failure, metadata).await;
+        }
+    }
 
-            let note_outcome = self.update_tx.send(command.clone()).await;
+    async fn do_note_failure(
+        &self, update_saga_id: &str, zone: &LocationZoneCode, failure: LocationUpdateFailure,
+        metadata: HashMap<String, String>,
+    ) {
+        let command = model::CommandEnvelope::new_with_metadata(
+            update_saga_id,
+            UpdateLocationsCommand::NoteLocationUpdateFailure(zone.clone(), failure),
+            metadata,
+        );
+
+        let note_outcome = self.update_tx.send(command.clone()).await;
+        tracing::debug!(
+            ?note_outcome,
+            ?command,
+            "sending failure note command to update saga channel"
+        );
+        if let Err(error) = note_outcome {
             tracing::error!(
-                ?note_outcome,
-                ?command,
-                "sending failure note command to update saga channel"
+                ?error,
+                "failed to update saga on zone command failure: {zone}"
             );
-            if let Err(error) = note_outcome {
-                tracing::error!(
-                    ?error,
-                    "failed to update saga on zone command failure: {zone}"
-                );
-            }
         }
     }
 }
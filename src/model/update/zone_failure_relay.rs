@@ -0,0 +1,159 @@
+use super::{LocationUpdateFailure, LocationUpdatedStep, UpdateLocations, UpdateLocationsCommand};
+use crate::model::zone::LocationZoneCommand;
+use crate::model::{self, CommandFailure, LocationZone, LocationZoneCode};
+use std::fmt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Routes the failures of location zone commands issued by an update saga back to the saga, keyed
+/// by the commands' correlation, so the saga notes the zone's failed update step right away rather
+/// than waiting out its deadline. This includes a failed `NoteAlerts`, since the saga notes a
+/// zone's alert step only from the alert events the zone records.
+pub struct ZoneFailureRelay {
+    failure_rx: mpsc::Receiver<CommandFailure<LocationZone>>,
+    update_tx: mpsc::Sender<model::CommandEnvelope<UpdateLocations>>,
+}
+
+impl fmt::Debug for ZoneFailureRelay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZoneFailureRelay").finish()
+    }
+}
+
+impl ZoneFailureRelay {
+    pub fn new(
+        failure_rx: mpsc::Receiver<CommandFailure<LocationZone>>,
+        update_tx: mpsc::Sender<model::CommandEnvelope<UpdateLocations>>,
+    ) -> Self {
+        Self { failure_rx, update_tx }
+    }
+
+    pub fn run(self) -> JoinHandle<()> {
+        tokio::spawn(async move { self.do_run().await })
+    }
+
+    async fn do_run(mut self) {
+        while let Some(failure) = self.failure_rx.recv().await {
+            self.do_relay(failure).await;
+        }
+
+        tracing::info!("zone command failure channel closed - stopping");
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn do_relay(&self, failure: CommandFailure<LocationZone>) {
+        let step = match update_step_of(failure.command.payload()) {
+            Some(step) => step,
+            None => return,
+        };
+
+        let zone = LocationZoneCode::new(failure.command.target_id());
+        let reason = LocationUpdateFailure::from_zone_error(step, &failure.error);
+        let command = model::CommandEnvelope::new_with_metadata(
+            failure.correlation,
+            UpdateLocationsCommand::NoteLocationUpdateFailure(zone, reason),
+            failure.command.metadata().clone(),
+        );
+
+        if let Err(error) = self.update_tx.send(command).await {
+            tracing::error!(
+                ?error,
+                "failed to note zone command failure with update saga"
+            );
+        }
+    }
+}
+
+/// The update step performed by the zone command, if any.
+fn update_step_of(command: &LocationZoneCommand) -> Option<LocationUpdatedStep> {
+    match command {
+        LocationZoneCommand::Observe => Some(LocationUpdatedStep::Observation),
+        LocationZoneCommand::Forecast => Some(LocationUpdatedStep::Forecast),
+        LocationZoneCommand::NoteAlerts(_) => Some(LocationUpdatedStep::Alert),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::update::UpdateFailureKind;
+    use crate::model::zone::LocationServices;
+    use crate::model::{CommandRelay, ObservationTolerances, CORRELATION_METADATA};
+    use crate::services::noaa::{HappyPathWeatherServices, NoaaWeatherServices};
+    use cqrs_es::mem_store::MemStore;
+    use cqrs_es::CqrsFramework;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_update_step_of_zone_command() {
+        assert_eq!(
+            update_step_of(&LocationZoneCommand::Observe),
+            Some(LocationUpdatedStep::Observation)
+        );
+        assert_eq!(
+            update_step_of(&LocationZoneCommand::Forecast),
+            Some(LocationUpdatedStep::Forecast)
+        );
+        assert_eq!(
+            update_step_of(&LocationZoneCommand::NoteAlerts(vec![])),
+            Some(LocationUpdatedStep::Alert)
+        );
+        assert_eq!(update_step_of(&LocationZoneCommand::Pause), None);
+        assert_eq!(update_step_of(&LocationZoneCommand::ExpireAlerts), None);
+    }
+
+    #[tokio::test]
+    async fn test_failed_correlated_zone_command_noted_with_update_saga() {
+        let zone_agg = Arc::new(CqrsFramework::new(
+            MemStore::<LocationZone>::default(),
+            vec![],
+            LocationServices::new(
+                NoaaWeatherServices::HappyPath(HappyPathWeatherServices),
+                ObservationTolerances::default(),
+            ),
+        ));
+        let (location_tx, location_rx) = mpsc::channel(8);
+        let (failure_tx, failure_rx) = mpsc::channel(8);
+        let (update_tx, mut update_rx) = mpsc::channel(8);
+        CommandRelay::new(zone_agg, location_rx).with_failure_tx(failure_tx).run();
+        ZoneFailureRelay::new(failure_rx, update_tx).run();
+
+        // unwatched zones reject their update commands
+        let correlated = maplit::hashmap! {
+            CORRELATION_METADATA.to_string() => "saga-1".to_string(),
+        };
+        let commands = vec![
+            model::CommandEnvelope::new("WAZ558", LocationZoneCommand::Observe),
+            model::CommandEnvelope::new_with_metadata(
+                "WAZ558",
+                LocationZoneCommand::Pause,
+                correlated.clone(),
+            ),
+            model::CommandEnvelope::new_with_metadata(
+                "WAC033",
+                LocationZoneCommand::Observe,
+                correlated,
+            ),
+        ];
+        for command in commands {
+            claim::assert_ok!(location_tx.send(command).await);
+        }
+        drop(location_tx);
+
+        let noted = claim::assert_some!(update_rx.recv().await);
+        assert_eq!(noted.target_id(), "saga-1");
+        match noted.payload() {
+            UpdateLocationsCommand::NoteLocationUpdateFailure(zone, failure) => {
+                assert_eq!(zone, &LocationZoneCode::new("WAC033"));
+                assert_eq!(failure.step, LocationUpdatedStep::Observation);
+                assert_eq!(failure.kind, UpdateFailureKind::Rejected);
+            },
+            command => panic!("unexpected update saga command: {command:?}"),
+        }
+
+        // neither the uncorrelated command nor the command without an update step is noted
+        assert!(update_rx.recv().await.is_none());
+    }
+}
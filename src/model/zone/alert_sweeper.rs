@@ -0,0 +1,88 @@
+use super::{LocationZoneAggregate, LocationZoneCommand, WEATHER_QUERY_VIEW};
+use crate::model;
+use crate::settings::AlertExpirySettings;
+use sql_query_builder as sql;
+use sqlx::PgPool;
+use std::fmt;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Periodically expires zone alerts whose expiry or end time has passed, so zones between
+/// weather updates do not keep serving stale alerts. Suspended zones keep their last known alerts.
+pub struct AlertExpirySweeper {
+    interval: Duration,
+    location_agg: LocationZoneAggregate,
+    db_pool: PgPool,
+}
+
+impl fmt::Debug for AlertExpirySweeper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlertExpirySweeper")
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl AlertExpirySweeper {
+    pub fn new(
+        settings: &AlertExpirySettings, location_agg: LocationZoneAggregate, db_pool: PgPool,
+    ) -> Self {
+        Self { interval: settings.interval, location_agg, db_pool }
+    }
+
+    pub fn run(self) -> JoinHandle<()> {
+        tokio::spawn(async move { self.do_run().await })
+    }
+
+    async fn do_run(self) {
+        let mut ticks = tokio::time::interval(self.interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticks.tick().await;
+            self.do_sweep().await;
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn do_sweep(&self) {
+        let zones = match self.find_zones_with_expired_alerts().await {
+            Ok(zones) => zones,
+            Err(error) => {
+                tracing::error!(?error, "failed to find zones with expired alerts");
+                return;
+            },
+        };
+
+        for zone in zones {
+            let outcome = self
+                .location_agg
+                .execute_with_metadata(
+                    &zone,
+                    LocationZoneCommand::ExpireAlerts,
+                    model::occurrence_metadata(),
+                )
+                .await;
+            if let Err(error) = outcome {
+                tracing::error!(?error, %zone, "failed to expire zone alerts");
+            }
+        }
+    }
+
+    async fn find_zones_with_expired_alerts(&self) -> Result<Vec<String>, sqlx::Error> {
+        let select_sql = sql::Select::new()
+            .select("view_id")
+            .from(WEATHER_QUERY_VIEW)
+            .where_clause("NOT COALESCE((payload ->> 'retired')::boolean, false)")
+            .where_clause("NOT COALESCE((payload ->> 'paused')::boolean, false)")
+            .where_clause(
+                "EXISTS (SELECT 1 FROM json_array_elements(payload -> 'alerts') AS alert WHERE \
+                 (alert ->> 'expires')::timestamptz <= now() OR (alert ->> 'ends')::timestamptz \
+                 <= now())",
+            )
+            .to_string();
+
+        let rows: Vec<(String,)> = sqlx::query_as(&select_sql).fetch_all(&self.db_pool).await?;
+        Ok(rows.into_iter().map(|(view_id,)| view_id).collect())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::model::LocationZoneCode;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -5,6 +6,9 @@ pub enum LocationZoneError {
     #[error("rejected command: {0}")]
     RejectedCommand(String),
 
+    #[error("weather provider does not know zone: {0}")]
+    UnknownZone(LocationZoneCode),
+
     #[error("{0}")]
     Noaa(#[from] crate::services::noaa::NoaaWeatherError),
 }
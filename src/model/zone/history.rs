@@ -0,0 +1,164 @@
+use crate::model::{LocationZone, LocationZoneCode, WeatherFrame};
+use crate::settings::ObservationHistorySettings;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cqrs_es::{EventEnvelope, Query};
+use serde::{Deserialize, Serialize};
+use sql_query_builder as sql;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use std::fmt;
+use utoipa::IntoParams;
+
+pub const OBSERVATION_HISTORY_TABLE: &str = "observation_history";
+
+/// Most observations served for a window, unless the request asks for fewer.
+pub const MAX_OBSERVATIONS_LIMIT: u32 = 1_000;
+
+/// Time window of a zone's observation history.
+#[derive(Debug, Default, Clone, PartialEq, Eq, IntoParams, Serialize, Deserialize)]
+#[into_params(parameter_in = Query)]
+pub struct ObservationWindow {
+    /// Earliest observation time, inclusive.
+    pub from: Option<DateTime<Utc>>,
+
+    /// Latest observation time, inclusive.
+    pub to: Option<DateTime<Utc>>,
+
+    /// Most observations to return, up to 1000; the most recent in the window are returned.
+    pub limit: Option<u32>,
+}
+
+impl ObservationWindow {
+    fn limit(&self) -> i64 {
+        i64::from(self.limit.unwrap_or(MAX_OBSERVATIONS_LIMIT).min(MAX_OBSERVATIONS_LIMIT))
+    }
+}
+
+/// Loads the zone's observations within the window, most recent first.
+#[tracing::instrument(level = "debug", skip(db_pool))]
+pub async fn load_observations(
+    db_pool: &PgPool, zone: &LocationZoneCode, window: &ObservationWindow,
+) -> Result<Vec<WeatherFrame>, sqlx::Error> {
+    let select_sql = sql::Select::new()
+        .select("payload")
+        .from(OBSERVATION_HISTORY_TABLE)
+        .where_clause("zone_code = $1")
+        .where_clause("($2::timestamptz IS NULL OR $2 <= observed_at)")
+        .where_clause("($3::timestamptz IS NULL OR observed_at <= $3)")
+        .order_by("observed_at DESC")
+        .limit("$4")
+        .to_string();
+
+    let rows: Vec<(Json<WeatherFrame>,)> = sqlx::query_as(&select_sql)
+        .bind(zone.as_ref())
+        .bind(window.from)
+        .bind(window.to)
+        .bind(window.limit())
+        .fetch_all(db_pool)
+        .await?;
+
+    Ok(rows.into_iter().map(|(Json(frame),)| frame).collect())
+}
+
+/// Records every observation added to a location zone in the observation history, trimming
+/// the zone's history to the retention limits as it goes.
+pub struct ObservationHistoryQuery {
+    db_pool: PgPool,
+    retention: ObservationHistorySettings,
+}
+
+impl fmt::Debug for ObservationHistoryQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservationHistoryQuery")
+            .field("retention", &self.retention)
+            .finish()
+    }
+}
+
+impl ObservationHistoryQuery {
+    pub fn new(db_pool: PgPool, retention: ObservationHistorySettings) -> Self {
+        Self { db_pool, retention }
+    }
+
+    async fn record(
+        &self, zone_code: &str, sequence: usize, frame: &WeatherFrame,
+    ) -> Result<(), sqlx::Error> {
+        let insert_sql = sql::Insert::new()
+            .insert_into(&format!(
+                "{OBSERVATION_HISTORY_TABLE} (zone_code, observed_at, sequence, payload)"
+            ))
+            .values("($1, ($2::json ->> 'timestamp')::timestamptz, $3, $2)")
+            .on_conflict("(zone_code, observed_at) DO NOTHING")
+            .to_string();
+
+        sqlx::query(&insert_sql)
+            .bind(zone_code)
+            .bind(Json(frame))
+            .bind(i64::try_from(sequence).unwrap_or(i64::MAX))
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn apply_retention(&self, zone_code: &str) -> Result<(), sqlx::Error> {
+        let max_age = self.retention.max_age.and_then(|age| chrono::Duration::from_std(age).ok());
+        if let Some(max_age) = max_age {
+            let delete_sql = sql::Delete::new()
+                .delete_from(OBSERVATION_HISTORY_TABLE)
+                .where_clause("zone_code = $1")
+                .where_clause("observed_at < $2")
+                .to_string();
+
+            sqlx::query(&delete_sql)
+                .bind(zone_code)
+                .bind(Utc::now() - max_age)
+                .execute(&self.db_pool)
+                .await?;
+        }
+
+        if let Some(max_frames) = self.retention.max_frames_per_zone {
+            // drops the observations at and beyond the first one past the limit
+            let delete_sql = sql::Delete::new()
+                .delete_from(OBSERVATION_HISTORY_TABLE)
+                .where_clause("zone_code = $1")
+                .where_clause(&format!(
+                    "observed_at <= (SELECT observed_at FROM {OBSERVATION_HISTORY_TABLE} WHERE \
+                     zone_code = $1 ORDER BY observed_at DESC OFFSET $2 LIMIT 1)"
+                ))
+                .to_string();
+
+            sqlx::query(&delete_sql)
+                .bind(zone_code)
+                .bind(i64::from(max_frames))
+                .execute(&self.db_pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Query<LocationZone> for ObservationHistoryQuery {
+    async fn dispatch(&self, zone_code: &str, events: &[EventEnvelope<LocationZone>]) {
+        use super::LocationZoneEvent as Evt;
+
+        let mut recorded = false;
+        for event in events {
+            if let Evt::ObservationAdded(frame) = &event.payload {
+                if let Err(error) = self.record(zone_code, event.sequence, frame).await {
+                    tracing::error!(?error, %zone_code, "failed to record observation history");
+                    continue;
+                }
+                recorded = true;
+            }
+        }
+
+        if recorded {
+            if let Err(error) = self.apply_retention(zone_code).await {
+                tracing::error!(?error, %zone_code, "failed to trim observation history");
+            }
+        }
+    }
+}
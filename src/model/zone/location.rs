@@ -2,13 +2,16 @@ use crate::model::zone::errors::LocationZoneError;
 use crate::model::zone::service::LocationServices;
 use crate::model::zone::{LocationZoneCommand, LocationZoneEvent};
 use crate::model::{
-    AggregateState, LocationZoneCode, LocationZoneType, WeatherFrame, ZoneForecast,
+    AggregateState, LocationZoneCode, LocationZoneType, ObservationTolerances, WeatherAlert,
+    WeatherFrame, ZoneForecast, ZoneMetadata,
 };
 use crate::services::noaa::ZoneWeatherApi;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use cqrs_es::Aggregate;
 use postgres_es::PostgresCqrs;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use tagid::Label;
 
@@ -50,6 +53,8 @@ impl Aggregate for LocationZone {
 enum LocationZoneState {
     Quiescent(QuiescentLocationZone),
     Active(Box<ActiveLocationZone>),
+    Suspended(SuspendedLocationZone),
+    Retired(RetiredLocationZone),
 }
 
 impl Default for LocationZoneState {
@@ -72,6 +77,8 @@ impl AggregateState for LocationZoneState {
         match self {
             Self::Quiescent(state) => state.handle(command, services).await,
             Self::Active(state) => state.handle(command, services).await,
+            Self::Suspended(state) => state.handle(command, services).await,
+            Self::Retired(state) => state.handle(command, services).await,
         }
     }
 
@@ -79,10 +86,39 @@ impl AggregateState for LocationZoneState {
         match self {
             Self::Quiescent(state) => state.apply(event),
             Self::Active(state) => state.apply(event),
+            Self::Suspended(state) => state.apply(event),
+            Self::Retired(state) => state.apply(event),
         }
     }
 }
 
+/// Validates the zone with the weather provider before the zone is watched.
+async fn watch_zone(
+    zone_code: LocationZoneCode, zone_type: LocationZoneType, services: &LocationServices,
+) -> Result<Vec<LocationZoneEvent>, LocationZoneError> {
+    let metadata = services
+        .zone_metadata(zone_type, &zone_code)
+        .await?
+        .ok_or_else(|| LocationZoneError::UnknownZone(zone_code.clone()))?;
+
+    Ok(vec![
+        LocationZoneEvent::ZoneSet(zone_code),
+        LocationZoneEvent::ZoneMetadataUpdated(metadata),
+    ])
+}
+
+fn activate_zone(zone_code: LocationZoneCode) -> LocationZoneState {
+    LocationZoneState::Active(Box::new(ActiveLocationZone {
+        zone_id: zone_code,
+        metadata: None,
+        weather: None,
+        forecast: None,
+        alerts: BTreeMap::new(),
+        stations: BTreeMap::new(),
+        watchers: BTreeSet::new(),
+    }))
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 struct QuiescentLocationZone;
 
@@ -96,13 +132,15 @@ impl AggregateState for QuiescentLocationZone {
 
     #[tracing::instrument(level = "trace")]
     async fn handle(
-        &self, command: Self::Command, _services: &Self::Services,
+        &self, command: Self::Command, services: &Self::Services,
     ) -> Result<Vec<Self::Event>, Self::Error> {
         match command {
-            LocationZoneCommand::WatchZone(zone_code) => {
-                Ok(vec![LocationZoneEvent::ZoneSet(zone_code)])
+            LocationZoneCommand::WatchZone(zone_code, zone_type) => {
+                watch_zone(zone_code, zone_type, services).await
             },
 
+            LocationZoneCommand::Retire | LocationZoneCommand::Unwatch(_) => Ok(vec![]),
+
             cmd => Err(LocationZoneError::RejectedCommand(format!(
                 "LocationZone cannot handle command until it targets a zone: {cmd:?}"
             ))),
@@ -112,14 +150,7 @@ impl AggregateState for QuiescentLocationZone {
     #[tracing::instrument(level = "trace")]
     fn apply(&self, event: Self::Event) -> Option<Self::State> {
         match event {
-            LocationZoneEvent::ZoneSet(zone_code) => {
-                Some(Self::State::Active(Box::new(ActiveLocationZone {
-                    zone_id: zone_code,
-                    weather: None,
-                    forecast: None,
-                    active_alert: false,
-                })))
-            },
+            LocationZoneEvent::ZoneSet(zone_code) => Some(activate_zone(zone_code)),
 
             event => {
                 tracing::warn!(?event, "invalid quiescent location zone event -- ignored");
@@ -132,9 +163,132 @@ impl AggregateState for QuiescentLocationZone {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct ActiveLocationZone {
     pub zone_id: LocationZoneCode,
+
+    #[serde(default)]
+    pub metadata: Option<ZoneMetadata>,
+
     pub weather: Option<WeatherFrame>,
     pub forecast: Option<ZoneForecast>,
-    pub active_alert: bool,
+
+    /// Active alerts keyed by CAP identifier.
+    #[serde(default)]
+    pub alerts: BTreeMap<String, WeatherAlert>,
+
+    /// Latest reading of each station reporting for the zone, keyed by station identifier.
+    #[serde(default)]
+    pub stations: BTreeMap<String, WeatherFrame>,
+
+    /// Watchlists monitoring the zone. Zones watched before watchers were recorded have none.
+    #[serde(default)]
+    pub watchers: BTreeSet<String>,
+}
+
+impl ActiveLocationZone {
+    /// Zone type captured when the zone was validated, or as implied by the code for zones watched
+    /// before their metadata was recorded.
+    fn zone_type(&self) -> LocationZoneType {
+        self.metadata
+            .as_ref()
+            .map_or_else(|| self.zone_id.implied_zone_type(), |m| m.zone_type)
+    }
+
+    /// Compares the alerts now active for the zone with those noted before. Alerts past their
+    /// expiry are expired rather than noted. Noting no change confirms the zone's alerts, so the
+    /// zone records an event for every noting of its alerts.
+    fn note_alerts(
+        &self, alerts: Vec<WeatherAlert>, as_of: DateTime<Utc>,
+    ) -> Vec<LocationZoneEvent> {
+        let alerts: BTreeMap<_, _> = alerts
+            .into_iter()
+            .filter(|alert| !alert.is_expired(as_of))
+            .map(|alert| (alert.id.clone(), alert))
+            .collect();
+
+        let expired = self.expire_alerts(as_of);
+
+        let cleared = self
+            .alerts
+            .iter()
+            .filter(|(id, alert)| !alerts.contains_key(*id) && !alert.is_expired(as_of))
+            .map(|(id, _)| LocationZoneEvent::AlertCleared(id.clone()));
+
+        let noted = alerts.into_values().filter_map(|alert| match self.alerts.get(&alert.id) {
+            None => Some(LocationZoneEvent::AlertActivated(alert)),
+            Some(prior) if prior != &alert => Some(LocationZoneEvent::AlertUpdated(alert)),
+            Some(_) => None,
+        });
+
+        let mut changes: Vec<_> = cleared.chain(noted).collect();
+        if changes.is_empty() {
+            changes.push(LocationZoneEvent::AlertsConfirmed);
+        }
+
+        expired.into_iter().chain(changes).collect()
+    }
+
+    /// Whether the stations reporting for the zone, or any station's reading beyond tolerance,
+    /// changed since the stations were last observed.
+    fn stations_changed(
+        &self, stations: &BTreeMap<String, WeatherFrame>, tolerances: &ObservationTolerances,
+    ) -> bool {
+        stations.len() != self.stations.len()
+            || stations.iter().any(|(station, frame)| {
+                self.stations
+                    .get(station)
+                    .map_or(true, |prior| !tolerances.is_unchanged(prior, frame))
+            })
+    }
+
+    fn expire_alerts(&self, as_of: DateTime<Utc>) -> Vec<LocationZoneEvent> {
+        self.alerts
+            .iter()
+            .filter(|(_, alert)| alert.is_expired(as_of))
+            .map(|(id, _)| LocationZoneEvent::AlertExpired(id.clone()))
+            .collect()
+    }
+
+    fn with_alerts(&self, alerts: BTreeMap<String, WeatherAlert>) -> LocationZoneState {
+        LocationZoneState::Active(Box::new(Self { alerts, ..self.clone() }))
+    }
+
+    fn watch(&self, watchlist: String) -> Vec<LocationZoneEvent> {
+        if self.watchers.contains(&watchlist) {
+            vec![]
+        } else {
+            vec![LocationZoneEvent::ZoneWatched(watchlist)]
+        }
+    }
+
+    /// Retires the zone once its last watcher stops monitoring it. Unwatching a zone without
+    /// recorded watchers retires it, as before watchers were recorded.
+    fn unwatch(&self, watchlist: String) -> Vec<LocationZoneEvent> {
+        if !self.watchers.is_empty() && !self.watchers.contains(&watchlist) {
+            tracing::debug!(%watchlist, "{} zone not watched by watchlist - ignoring", self.zone_id);
+            return vec![];
+        }
+
+        let is_last_watcher = self.watchers.iter().all(|watcher| watcher == &watchlist);
+        let mut events = vec![LocationZoneEvent::ZoneUnwatched(watchlist)];
+        if is_last_watcher {
+            events.push(LocationZoneEvent::ZoneRetired);
+        }
+        events
+    }
+
+    fn with_watchers(&self, watchers: BTreeSet<String>) -> Self {
+        Self { watchers, ..self.clone() }
+    }
+
+    /// Applies a change in the zone's watchers, if the event is one.
+    fn apply_watchers(&self, event: &LocationZoneEvent) -> Option<Self> {
+        let mut watchers = self.watchers.clone();
+        match event {
+            LocationZoneEvent::ZoneWatched(watchlist) => watchers.insert(watchlist.clone()),
+            LocationZoneEvent::ZoneUnwatched(watchlist) => watchers.remove(watchlist),
+            _ => return None,
+        };
+        Some(self.with_watchers(watchers))
+    }
 }
 
 #[async_trait]
@@ -151,64 +305,118 @@ impl AggregateState for ActiveLocationZone {
     ) -> Result<Vec<Self::Event>, Self::Error> {
         match command {
             LocationZoneCommand::Observe => {
-                let frame = services.zone_observation(&self.zone_id).await?;
-                Ok(vec![LocationZoneEvent::ObservationAdded(Box::new(frame))])
+                let observation = services.zone_observation(&self.zone_id).await?;
+                let frame = observation.frame;
+                let tolerances = services.observation_tolerances();
+                let mut events = match &self.weather {
+                    Some(current) if tolerances.is_unchanged(current, &frame) => {
+                        tracing::debug!("{} observation unchanged - confirming", self.zone_id);
+                        vec![LocationZoneEvent::ObservationConfirmed(frame.timestamp)]
+                    },
+                    _ => vec![LocationZoneEvent::ObservationAdded(Box::new(frame))],
+                };
+
+                let stations = observation.stations;
+                if !stations.is_empty() && self.stations_changed(&stations, tolerances) {
+                    events.push(LocationZoneEvent::StationsObserved(stations));
+                }
+
+                Ok(events)
             },
 
             LocationZoneCommand::Forecast => {
-                let forecast =
-                    services.zone_forecast(LocationZoneType::Forecast, &self.zone_id).await?;
+                let forecast = services.zone_forecast(self.zone_type(), &self.zone_id).await?;
                 Ok(vec![LocationZoneEvent::ForecastUpdated(forecast)])
             },
 
-            LocationZoneCommand::NoteAlert(alert) => {
-                let event = match (self.active_alert, alert) {
-                    (false, Some(alert)) => Some(LocationZoneEvent::AlertActivated(alert)),
-                    (true, None) => Some(LocationZoneEvent::AlertDeactivated),
-                    _ => None,
-                };
+            LocationZoneCommand::NoteAlerts(alerts) => Ok(self.note_alerts(alerts, Utc::now())),
 
-                Ok(event.into_iter().collect())
-            },
+            LocationZoneCommand::ExpireAlerts => Ok(self.expire_alerts(Utc::now())),
 
-            LocationZoneCommand::WatchZone(new_zone_code) => {
+            LocationZoneCommand::WatchZone(new_zone_code, _) => {
                 tracing::debug!("{new_zone_code} zone watch set before - ignoring");
                 Ok(vec![])
             },
+
+            LocationZoneCommand::Pause => Ok(vec![LocationZoneEvent::ZonePaused]),
+
+            LocationZoneCommand::Resume => {
+                tracing::debug!("{} zone not suspended - ignoring", self.zone_id);
+                Ok(vec![])
+            },
+
+            LocationZoneCommand::Watch(watchlist) => Ok(self.watch(watchlist)),
+
+            LocationZoneCommand::Unwatch(watchlist) => Ok(self.unwatch(watchlist)),
+
+            LocationZoneCommand::Retire => Ok(vec![LocationZoneEvent::ZoneRetired]),
         }
     }
 
     #[tracing::instrument(level = "trace")]
     fn apply(&self, event: Self::Event) -> Option<Self::State> {
         match event {
-            LocationZoneEvent::ObservationAdded(frame) => {
+            LocationZoneEvent::ZoneMetadataUpdated(metadata) => {
                 Some(LocationZoneState::Active(Box::new(Self {
-                    weather: Some(*frame),
+                    metadata: Some(metadata),
                     ..self.clone()
                 })))
             },
 
-            LocationZoneEvent::ForecastUpdated(forecast) => {
+            LocationZoneEvent::ObservationAdded(frame) => {
                 Some(LocationZoneState::Active(Box::new(Self {
-                    forecast: Some(forecast),
+                    weather: Some(*frame),
                     ..self.clone()
                 })))
             },
 
-            LocationZoneEvent::AlertActivated(_) => {
+            LocationZoneEvent::ObservationConfirmed(_) | LocationZoneEvent::AlertsConfirmed => None,
+
+            LocationZoneEvent::StationsObserved(stations) => {
                 Some(LocationZoneState::Active(Box::new(Self {
-                    active_alert: true,
+                    stations,
                     ..self.clone()
                 })))
             },
 
-            LocationZoneEvent::AlertDeactivated => {
+            LocationZoneEvent::ForecastUpdated(forecast) => {
                 Some(LocationZoneState::Active(Box::new(Self {
-                    active_alert: false,
+                    forecast: Some(forecast),
                     ..self.clone()
                 })))
             },
 
+            LocationZoneEvent::AlertActivated(alert) | LocationZoneEvent::AlertUpdated(alert) => {
+                let mut alerts = self.alerts.clone();
+                alerts.insert(alert.id.clone(), alert);
+                Some(self.with_alerts(alerts))
+            },
+
+            LocationZoneEvent::AlertCleared(id) | LocationZoneEvent::AlertExpired(id) => {
+                let mut alerts = self.alerts.clone();
+                alerts.remove(&id);
+                Some(self.with_alerts(alerts))
+            },
+
+            LocationZoneEvent::AlertDeactivated => Some(self.with_alerts(BTreeMap::new())),
+
+            LocationZoneEvent::ZoneWatched(_) | LocationZoneEvent::ZoneUnwatched(_) => self
+                .apply_watchers(&event)
+                .map(|zone| LocationZoneState::Active(Box::new(zone))),
+
+            LocationZoneEvent::ZonePaused => {
+                Some(LocationZoneState::Suspended(SuspendedLocationZone {
+                    zone: Box::new(self.clone()),
+                }))
+            },
+
+            LocationZoneEvent::ZoneRetired => {
+                Some(LocationZoneState::Retired(RetiredLocationZone {
+                    zone_id: self.zone_id.clone(),
+                    metadata: self.metadata.clone(),
+                }))
+            },
+
             event => {
                 tracing::warn!(?event, "invalid active location zone event -- ignored");
                 None
@@ -216,3 +424,480 @@ impl AggregateState for ActiveLocationZone {
         }
     }
 }
+
+/// A zone whose updates are suspended; e.g., under maintenance or monitored only seasonally. The
+/// zone stays watched and keeps its last known weather, which it resumes updating from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SuspendedLocationZone {
+    pub zone: Box<ActiveLocationZone>,
+}
+
+#[async_trait]
+impl AggregateState for SuspendedLocationZone {
+    type State = LocationZoneState;
+    type Command = <LocationZone as Aggregate>::Command;
+    type Event = <LocationZone as Aggregate>::Event;
+    type Error = <LocationZone as Aggregate>::Error;
+    type Services = <LocationZone as Aggregate>::Services;
+
+    #[tracing::instrument(level = "trace")]
+    async fn handle(
+        &self, command: Self::Command, _services: &Self::Services,
+    ) -> Result<Vec<Self::Event>, Self::Error> {
+        match command {
+            LocationZoneCommand::Resume => Ok(vec![LocationZoneEvent::ZoneResumed]),
+
+            LocationZoneCommand::Watch(watchlist) => Ok(self.zone.watch(watchlist)),
+
+            LocationZoneCommand::Unwatch(watchlist) => Ok(self.zone.unwatch(watchlist)),
+
+            LocationZoneCommand::Retire => Ok(vec![LocationZoneEvent::ZoneRetired]),
+
+            cmd => {
+                tracing::debug!(command=?cmd, "{} zone suspended - ignoring", self.zone.zone_id);
+                Ok(vec![])
+            },
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn apply(&self, event: Self::Event) -> Option<Self::State> {
+        match event {
+            LocationZoneEvent::ZoneResumed => Some(LocationZoneState::Active(self.zone.clone())),
+
+            LocationZoneEvent::ZoneWatched(_) | LocationZoneEvent::ZoneUnwatched(_) => {
+                self.zone.apply_watchers(&event).map(|zone| {
+                    LocationZoneState::Suspended(SuspendedLocationZone { zone: Box::new(zone) })
+                })
+            },
+
+            LocationZoneEvent::ZoneRetired => {
+                Some(LocationZoneState::Retired(RetiredLocationZone {
+                    zone_id: self.zone.zone_id.clone(),
+                    metadata: self.zone.metadata.clone(),
+                }))
+            },
+
+            event => {
+                tracing::warn!(?event, "invalid suspended location zone event -- ignored");
+                None
+            },
+        }
+    }
+}
+
+/// A zone no longer monitored by any watchlist. Watching the zone again reactivates it afresh.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RetiredLocationZone {
+    pub zone_id: LocationZoneCode,
+
+    #[serde(default)]
+    pub metadata: Option<ZoneMetadata>,
+}
+
+#[async_trait]
+impl AggregateState for RetiredLocationZone {
+    type State = LocationZoneState;
+    type Command = <LocationZone as Aggregate>::Command;
+    type Event = <LocationZone as Aggregate>::Event;
+    type Error = <LocationZone as Aggregate>::Error;
+    type Services = <LocationZone as Aggregate>::Services;
+
+    #[tracing::instrument(level = "trace")]
+    async fn handle(
+        &self, command: Self::Command, services: &Self::Services,
+    ) -> Result<Vec<Self::Event>, Self::Error> {
+        match command {
+            LocationZoneCommand::WatchZone(zone_code, zone_type) => {
+                watch_zone(zone_code, zone_type, services).await
+            },
+
+            // a watchlist re-adding the zone as another's forgetting retired it
+            LocationZoneCommand::Watch(watchlist) => {
+                let mut events = vec![LocationZoneEvent::ZoneSet(self.zone_id.clone())];
+                events.extend(self.metadata.clone().map(LocationZoneEvent::ZoneMetadataUpdated));
+                events.push(LocationZoneEvent::ZoneWatched(watchlist));
+                Ok(events)
+            },
+
+            LocationZoneCommand::Retire | LocationZoneCommand::Unwatch(_) => {
+                tracing::debug!("{} zone already retired - ignoring", self.zone_id);
+                Ok(vec![])
+            },
+
+            LocationZoneCommand::ExpireAlerts => Ok(vec![]),
+
+            cmd => Err(LocationZoneError::RejectedCommand(format!(
+                "retired LocationZone cannot handle command until it is watched again: {cmd:?}"
+            ))),
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    fn apply(&self, event: Self::Event) -> Option<Self::State> {
+        match event {
+            LocationZoneEvent::ZoneSet(zone_code) => Some(activate_zone(zone_code)),
+
+            event => {
+                tracing::warn!(?event, "invalid retired location zone event -- ignored");
+                None
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::noaa::{HappyPathWeatherServices, NoaaWeatherServices};
+    use cqrs_es::test::TestFramework;
+
+    fn test_services() -> LocationServices {
+        LocationServices::new(
+            NoaaWeatherServices::HappyPath(HappyPathWeatherServices),
+            ObservationTolerances::default(),
+        )
+    }
+
+    fn test_alert(id: &str, expires: DateTime<Utc>) -> WeatherAlert {
+        use crate::model::{
+            AlertCategory, AlertCertainty, AlertMessageType, AlertResponse, AlertSeverity,
+            AlertStatus, AlertUrgency,
+        };
+
+        WeatherAlert {
+            id: id.to_string(),
+            affected_zones: vec![LocationZoneCode::new("WAZ558")],
+            status: AlertStatus::Actual,
+            message_type: AlertMessageType::Alert,
+            sent: expires - chrono::Duration::hours(2),
+            effective: expires - chrono::Duration::hours(2),
+            onset: None,
+            expires,
+            ends: None,
+            category: AlertCategory::Met,
+            severity: AlertSeverity::Moderate,
+            certainty: AlertCertainty::Likely,
+            urgency: AlertUrgency::Expected,
+            event: "Wind Advisory".to_string(),
+            headline: "Wind Advisory issued".to_string(),
+            description: "Southwest winds 20 to 30 mph.".to_string(),
+            instruction: None,
+            response: AlertResponse::Prepare,
+        }
+    }
+
+    /// Readings matching those of the happy path observation.
+    fn observed_frame(temperature: f32) -> WeatherFrame {
+        use crate::model::{QualityControl, QuantitativeValue};
+
+        WeatherFrame {
+            timestamp: iso8601_timestamp::Timestamp::now_utc(),
+            temperature: Some(QuantitativeValue {
+                value: temperature,
+                max_value: 80.0,
+                min_value: 60.0,
+                unit_code: "DegreesF".into(),
+                quality_control: QualityControl::V,
+            }),
+            dewpoint: Some(QuantitativeValue {
+                value: 33.2,
+                max_value: 36.3,
+                min_value: 26.2,
+                unit_code: "DegreesF".into(),
+                quality_control: QualityControl::C,
+            }),
+            wind_direction: None,
+            wind_speed: None,
+            wind_gust: None,
+            barometric_pressure: None,
+            sea_level_pressure: None,
+            visibility: None,
+            max_temperature_last_24_hours: None,
+            min_temperature_last_24_hours: None,
+            precipitation_last_hour: None,
+            precipitation_last_3_hours: None,
+            precipitation_last_6_hours: None,
+            relative_humidity: None,
+            wind_chill: None,
+            heat_index: None,
+        }
+    }
+
+    fn observed_zone(station_temperature: f32) -> Vec<LocationZoneEvent> {
+        let frame = observed_frame(72.0);
+        let station = observed_frame(station_temperature);
+        vec![
+            LocationZoneEvent::ZoneSet(LocationZoneCode::new("WAZ558")),
+            LocationZoneEvent::ObservationAdded(Box::new(frame)),
+            LocationZoneEvent::StationsObserved(BTreeMap::from([("KHPP".to_string(), station)])),
+        ]
+    }
+
+    fn active_zone_with_alerts(alerts: &[WeatherAlert]) -> Vec<LocationZoneEvent> {
+        let mut events = vec![LocationZoneEvent::ZoneSet(LocationZoneCode::new("WAZ558"))];
+        events.extend(alerts.iter().cloned().map(LocationZoneEvent::AlertActivated));
+        events
+    }
+
+    #[test]
+    fn test_watch_zone_records_zone_metadata() {
+        let zone = LocationZoneCode::new("WAZ558");
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given_no_previous_events()
+            .when(LocationZoneCommand::WatchZone(
+                zone.clone(),
+                LocationZoneType::Forecast,
+            ))
+            .then_expect_events(vec![
+                LocationZoneEvent::ZoneSet(zone.clone()),
+                LocationZoneEvent::ZoneMetadataUpdated(ZoneMetadata {
+                    zone_code: zone,
+                    zone_type: LocationZoneType::Forecast,
+                    name: "Happy Path".to_string(),
+                    state: Some("WA".to_string()),
+                }),
+            ]);
+    }
+
+    #[test]
+    fn test_unwatch_by_last_watchlist_retires_zone() {
+        let zone = LocationZoneCode::new("WAZ558");
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(vec![
+                LocationZoneEvent::ZoneSet(zone),
+                LocationZoneEvent::ZoneWatched("default".to_string()),
+            ])
+            .when(LocationZoneCommand::Unwatch("default".to_string()))
+            .then_expect_events(vec![
+                LocationZoneEvent::ZoneUnwatched("default".to_string()),
+                LocationZoneEvent::ZoneRetired,
+            ]);
+    }
+
+    #[test]
+    fn test_unwatch_keeps_zone_watched_by_other_watchlist() {
+        let zone = LocationZoneCode::new("WAZ558");
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(vec![
+                LocationZoneEvent::ZoneSet(zone),
+                LocationZoneEvent::ZoneWatched("default".to_string()),
+                LocationZoneEvent::ZoneWatched("west".to_string()),
+                LocationZoneEvent::ZonePaused,
+            ])
+            .when(LocationZoneCommand::Unwatch("default".to_string()))
+            .then_expect_events(vec![LocationZoneEvent::ZoneUnwatched(
+                "default".to_string(),
+            )]);
+    }
+
+    #[test]
+    fn test_watch_reactivates_retired_zone() {
+        let zone = LocationZoneCode::new("WAZ558");
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(vec![
+                LocationZoneEvent::ZoneSet(zone.clone()),
+                LocationZoneEvent::ZoneWatched("default".to_string()),
+                LocationZoneEvent::ZoneUnwatched("default".to_string()),
+                LocationZoneEvent::ZoneRetired,
+            ])
+            .when(LocationZoneCommand::Watch("west".to_string()))
+            .then_expect_events(vec![
+                LocationZoneEvent::ZoneSet(zone),
+                LocationZoneEvent::ZoneWatched("west".to_string()),
+            ]);
+    }
+
+    #[test]
+    fn test_pause_unwatched_zone_rejected() {
+        TestFramework::<LocationZone>::with(test_services())
+            .given(vec![
+                LocationZoneEvent::ZoneSet(LocationZoneCode::new("WAZ558")),
+                LocationZoneEvent::ZoneWatched("default".to_string()),
+                LocationZoneEvent::ZoneUnwatched("default".to_string()),
+                LocationZoneEvent::ZoneRetired,
+            ])
+            .when(LocationZoneCommand::Pause)
+            .then_expect_error_message(
+                "rejected command: retired LocationZone cannot handle command until it is watched \
+                 again: Pause",
+            );
+    }
+
+    #[test]
+    fn test_watch_unknown_zone_rejected() {
+        TestFramework::<LocationZone>::with(test_services())
+            .given_no_previous_events()
+            .when(LocationZoneCommand::WatchZone(
+                LocationZoneCode::new("XXZ9999"),
+                LocationZoneType::Forecast,
+            ))
+            .then_expect_error_message("weather provider does not know zone: XXZ9999");
+    }
+
+    #[test]
+    fn test_note_alerts_records_alert_changes() {
+        let expires = Utc::now() + chrono::Duration::hours(1);
+        let kept = test_alert("kept", expires);
+        let cleared = test_alert("cleared", expires);
+        let updated = WeatherAlert {
+            headline: "Wind Advisory extended".to_string(),
+            ..kept.clone()
+        };
+        let activated = test_alert("activated", expires);
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(active_zone_with_alerts(&[kept, cleared]))
+            .when(LocationZoneCommand::NoteAlerts(vec![
+                updated.clone(),
+                activated.clone(),
+            ]))
+            .then_expect_events(vec![
+                LocationZoneEvent::AlertCleared("cleared".to_string()),
+                LocationZoneEvent::AlertActivated(activated),
+                LocationZoneEvent::AlertUpdated(updated),
+            ]);
+    }
+
+    #[test]
+    fn test_note_unchanged_alerts_confirms_alerts() {
+        let alert = test_alert("kept", Utc::now() + chrono::Duration::hours(1));
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(active_zone_with_alerts(&[alert.clone()]))
+            .when(LocationZoneCommand::NoteAlerts(vec![alert]))
+            .then_expect_events(vec![LocationZoneEvent::AlertsConfirmed]);
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(active_zone_with_alerts(&[]))
+            .when(LocationZoneCommand::NoteAlerts(vec![]))
+            .then_expect_events(vec![LocationZoneEvent::AlertsConfirmed]);
+    }
+
+    #[test]
+    fn test_with_alerts_replaces_zone_alerts() {
+        let alert = test_alert("kept", Utc::now() + chrono::Duration::hours(1));
+        let zone = ActiveLocationZone {
+            zone_id: LocationZoneCode::new("WAZ558"),
+            metadata: None,
+            weather: None,
+            forecast: None,
+            alerts: BTreeMap::new(),
+            stations: BTreeMap::new(),
+            watchers: BTreeSet::new(),
+        };
+
+        let alerts = BTreeMap::from([(alert.id.clone(), alert)]);
+        match zone.with_alerts(alerts.clone()) {
+            LocationZoneState::Active(active) => {
+                assert_eq!(active.alerts, alerts);
+                assert_eq!(active.zone_id, zone.zone_id);
+            },
+            state => panic!("expected active zone but was: {state:?}"),
+        }
+    }
+
+    #[test]
+    fn test_alert_is_expired_from_expiry_or_end() {
+        let expires = Utc::now();
+        let alert = test_alert("advisory", expires);
+        assert!(!alert.is_expired(expires - chrono::Duration::seconds(1)));
+        assert!(alert.is_expired(expires));
+        assert!(alert.is_expired(expires + chrono::Duration::seconds(1)));
+
+        let ends = expires - chrono::Duration::minutes(10);
+        let ended = WeatherAlert { ends: Some(ends), ..alert };
+        assert!(!ended.is_expired(ends - chrono::Duration::seconds(1)));
+        assert!(ended.is_expired(ends));
+    }
+
+    #[test]
+    fn test_expire_alerts_as_of() {
+        let expires = Utc::now();
+        let alert = test_alert("advisory", expires);
+        let zone = ActiveLocationZone {
+            zone_id: LocationZoneCode::new("WAZ558"),
+            metadata: None,
+            weather: None,
+            forecast: None,
+            alerts: BTreeMap::from([(alert.id.clone(), alert)]),
+            stations: BTreeMap::new(),
+            watchers: BTreeSet::new(),
+        };
+
+        assert_eq!(
+            zone.expire_alerts(expires - chrono::Duration::seconds(1)),
+            vec![]
+        );
+        assert_eq!(
+            zone.expire_alerts(expires),
+            vec![LocationZoneEvent::AlertExpired("advisory".to_string())]
+        );
+        assert_eq!(
+            zone.expire_alerts(expires + chrono::Duration::seconds(1)),
+            vec![LocationZoneEvent::AlertExpired("advisory".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_expire_alerts_expires_only_past_alerts() {
+        let expired = test_alert("expired", Utc::now() - chrono::Duration::minutes(5));
+        let active = test_alert("active", Utc::now() + chrono::Duration::hours(1));
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(active_zone_with_alerts(&[expired, active]))
+            .when(LocationZoneCommand::ExpireAlerts)
+            .then_expect_events(vec![LocationZoneEvent::AlertExpired("expired".to_string())]);
+    }
+
+    #[test]
+    fn test_expire_alerts_without_expired_alerts_records_nothing() {
+        let active = test_alert("active", Utc::now() + chrono::Duration::hours(1));
+
+        TestFramework::<LocationZone>::with(test_services())
+            .given(active_zone_with_alerts(&[active]))
+            .when(LocationZoneCommand::ExpireAlerts)
+            .then_expect_events(vec![]);
+    }
+
+    #[test]
+    fn test_observe_confirmed_with_unchanged_stations_records_only_confirmation() {
+        let events = TestFramework::<LocationZone>::with(test_services())
+            .given(observed_zone(72.0))
+            .when(LocationZoneCommand::Observe)
+            .inspect_result()
+            .expect("observe succeeds");
+
+        assert!(
+            matches!(
+                events.as_slice(),
+                [LocationZoneEvent::ObservationConfirmed(_)]
+            ),
+            "unexpected events: {events:?}"
+        );
+    }
+
+    #[test]
+    fn test_observe_confirmed_with_changed_station_records_stations() {
+        let events = TestFramework::<LocationZone>::with(test_services())
+            .given(observed_zone(65.0))
+            .when(LocationZoneCommand::Observe)
+            .inspect_result()
+            .expect("observe succeeds");
+
+        assert!(
+            matches!(
+                events.as_slice(),
+                [
+                    LocationZoneEvent::ObservationConfirmed(_),
+                    LocationZoneEvent::StationsObserved(stations),
+                ] if stations.keys().eq(["KHPP"])
+            ),
+            "unexpected events: {events:?}"
+        );
+    }
+}
@@ -1,17 +1,33 @@
+mod alert_sweeper;
 mod errors;
+mod history;
 mod location;
 mod protocol;
 mod queries;
+mod replay;
 mod service;
+mod watchers;
 
+pub use alert_sweeper::AlertExpirySweeper;
 pub use errors::LocationZoneError;
+pub use history::{
+    load_observations, ObservationHistoryQuery, ObservationWindow, MAX_OBSERVATIONS_LIMIT,
+    OBSERVATION_HISTORY_TABLE,
+};
 pub use location::{LocationZone, LocationZoneAggregate};
 pub use protocol::{LocationZoneCommand, LocationZoneEvent};
-pub use queries::{WeatherQuery, WeatherView, WeatherViewProjection, WEATHER_QUERY_VIEW};
+pub use queries::{
+    find_suspended_zones, WeatherQuery, WeatherView, WeatherViewProjection, ZoneStationsView,
+    WEATHER_QUERY_VIEW,
+};
+pub use replay::replay_weather_view;
 pub use service::LocationServices;
+pub use watchers::reconcile_zone_watchers;
 
+use crate::model::snapshots::make_postgres_cqrs;
 use crate::model::{EventBroadcastQuery, TracingQuery};
 use crate::services::noaa::NoaaWeatherServices;
+use crate::settings::{ObservationChangeSettings, ObservationHistorySettings};
 use cqrs_es::Query;
 use postgres_es::PostgresViewRepository;
 use sqlx::PgPool;
@@ -19,7 +35,8 @@ use std::sync::Arc;
 
 pub fn make_location_zone_aggregate_view(
     location_broadcast_query: EventBroadcastQuery<LocationZone>, noaa: NoaaWeatherServices,
-    db_pool: PgPool,
+    observation_history: ObservationHistorySettings, observation_change: ObservationChangeSettings,
+    snapshot_interval: Option<usize>, db_pool: PgPool,
 ) -> (LocationZoneAggregate, WeatherViewProjection) {
     let location_zone_tracing_query = TracingQuery::<LocationZone>::default();
     let weather_view = Arc::new(PostgresViewRepository::new(
@@ -31,16 +48,21 @@ pub fn make_location_zone_aggregate_view(
         |err| tracing::error!(error=?err, "weather query failed"),
     ));
 
+    let observation_history_query =
+        ObservationHistoryQuery::new(db_pool.clone(), observation_history);
+
     let location_queries: Vec<Box<dyn Query<LocationZone>>> = vec![
         Box::new(location_broadcast_query),
         Box::new(location_zone_tracing_query),
         Box::new(weather_query),
+        Box::new(observation_history_query),
     ];
-    let location_services = LocationServices::new(noaa);
-    let agg = Arc::new(postgres_es::postgres_cqrs(
+    let location_services = LocationServices::new(noaa, observation_change.tolerances);
+    let agg = Arc::new(make_postgres_cqrs(
         db_pool,
         location_queries,
         location_services,
+        snapshot_interval,
     ));
 
     (agg, weather_view)
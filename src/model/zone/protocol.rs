@@ -1,14 +1,34 @@
-use crate::model::{LocationZoneCode, WeatherAlert, WeatherFrame, ZoneForecast};
+use crate::model::{
+    EventUpcasterRegistry, LocationZoneCode, LocationZoneType, VersionedEvent, WeatherAlert,
+    WeatherFrame, ZoneForecast, ZoneMetadata,
+};
 use cqrs_es::DomainEvent;
+use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use strum_macros::Display;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LocationZoneCommand {
-    WatchZone(LocationZoneCode),
+    WatchZone(LocationZoneCode, LocationZoneType),
     Observe,
     Forecast,
-    NoteAlert(Option<WeatherAlert>),
+    /// Note the alerts currently active for the zone, replacing those noted before.
+    NoteAlerts(Vec<WeatherAlert>),
+
+    /// Expire the zone's alerts whose expiry or end time has passed.
+    ExpireAlerts,
+
+    /// Suspend the zone's updates, keeping its last known weather until it is resumed.
+    Pause,
+    Resume,
+
+    /// Note the watchlist, by identifier, monitors the zone.
+    Watch(String),
+
+    /// Note the watchlist no longer monitors the zone, which retires once no watchlist does.
+    Unwatch(String),
+    Retire,
 }
 
 const VERSION: &str = "1.0";
@@ -17,10 +37,38 @@ const VERSION: &str = "1.0";
 #[strum(serialize_all = "snake_case")]
 pub enum LocationZoneEvent {
     ZoneSet(LocationZoneCode),
+    ZoneMetadataUpdated(ZoneMetadata),
     ObservationAdded(Box<WeatherFrame>),
+
+    /// A new observation repeated the current observation, within tolerance, as of the timestamp.
+    ObservationConfirmed(Timestamp),
+
+    /// Latest reading of each station reporting for the zone, keyed by station identifier.
+    StationsObserved(BTreeMap<String, WeatherFrame>),
     ForecastUpdated(ZoneForecast),
     AlertActivated(WeatherAlert),
+    AlertUpdated(WeatherAlert),
+
+    /// The alert, by CAP identifier, is no longer active for the zone.
+    AlertCleared(String),
+
+    /// The alert, by CAP identifier, expired or its subject event ended.
+    AlertExpired(String),
+
+    /// The zone's active alerts were noted unchanged, other than alerts that expired.
+    AlertsConfirmed,
+
+    /// Alert status noted before zones kept multiple alerts; clears every alert of the zone.
     AlertDeactivated,
+    ZonePaused,
+    ZoneResumed,
+
+    /// The watchlist, by identifier, monitors the zone.
+    ZoneWatched(String),
+
+    /// The watchlist, by identifier, no longer monitors the zone.
+    ZoneUnwatched(String),
+    ZoneRetired,
 }
 
 impl DomainEvent for LocationZoneEvent {
@@ -32,3 +80,9 @@ impl DomainEvent for LocationZoneEvent {
         VERSION.to_string()
     }
 }
+
+impl VersionedEvent for LocationZoneEvent {
+    fn upcasters() -> EventUpcasterRegistry {
+        EventUpcasterRegistry::new()
+    }
+}
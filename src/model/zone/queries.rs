@@ -1,9 +1,15 @@
-use crate::model::{ForecastDetail, LocationZone, WeatherAlert, WeatherFrame};
+use crate::model::{
+    ForecastDetail, LocationZone, LocationZoneCode, WeatherAlert, WeatherFrame, ZoneMetadata,
+};
+use chrono::{DateTime, Utc};
 use cqrs_es::persist::GenericQuery;
 use cqrs_es::{EventEnvelope, View};
 use iso8601_timestamp::Timestamp;
 use postgres_es::PostgresViewRepository;
 use serde::{Deserialize, Serialize};
+use sql_query_builder as sql;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use utoipa::ToSchema;
 
@@ -14,6 +20,24 @@ pub type WeatherViewProjection = Arc<WeatherViewRepository>;
 
 pub type WeatherQuery = GenericQuery<WeatherViewRepository, WeatherView, LocationZone>;
 
+/// Finds which of the zones are suspended, per their weather views.
+#[tracing::instrument(level = "debug", skip(db_pool))]
+pub async fn find_suspended_zones(
+    db_pool: &PgPool, zones: &[&LocationZoneCode],
+) -> Result<HashSet<LocationZoneCode>, sqlx::Error> {
+    let select_sql = sql::Select::new()
+        .select("view_id")
+        .from(WEATHER_QUERY_VIEW)
+        .where_clause("view_id = ANY($1)")
+        .where_clause("COALESCE((payload ->> 'paused')::boolean, false)")
+        .to_string();
+
+    let zone_ids: Vec<&str> = zones.iter().map(|zone| zone.as_ref()).collect();
+    let rows: Vec<(String,)> =
+        sqlx::query_as(&select_sql).bind(zone_ids).fetch_all(db_pool).await?;
+    Ok(rows.into_iter().map(|(view_id,)| LocationZoneCode::new(view_id)).collect())
+}
+
 #[derive(Debug, Clone, PartialEq, ToSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WeatherView {
@@ -22,13 +46,33 @@ pub struct WeatherView {
     pub timestamp: Timestamp,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub alert: Option<WeatherAlert>,
+    pub zone: Option<ZoneMetadata>,
+
+    /// Active alerts, most severe and then most urgent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<WeatherAlert>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current: Option<WeatherFrame>,
 
+    /// Latest time a new observation repeated the current observation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_confirmed_at: Option<Timestamp>,
+
+    /// Latest reading of each station observing the zone, keyed by station identifier.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stations: BTreeMap<String, WeatherFrame>,
+
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub forecast: Vec<ForecastDetail>,
+
+    /// Set while the zone's updates are suspended, so its weather is as last known.
+    #[serde(default)]
+    pub paused: bool,
+
+    /// Set once the zone is no longer monitored by any watchlist.
+    #[serde(default)]
+    pub retired: bool,
 }
 
 impl Default for WeatherView {
@@ -36,9 +80,14 @@ impl Default for WeatherView {
         Self {
             zone_code: String::default(),
             timestamp: Timestamp::now_utc(),
-            alert: None,
+            zone: None,
+            alerts: Vec::new(),
             current: None,
+            current_confirmed_at: None,
+            stations: BTreeMap::new(),
             forecast: Vec::new(),
+            paused: false,
+            retired: false,
         }
     }
 }
@@ -51,6 +100,43 @@ impl WeatherView {
             ..Default::default()
         }
     }
+
+    /// Drops the alerts expired as of the time that the zone has yet to expire.
+    pub fn without_expired_alerts(mut self, as_of: DateTime<Utc>) -> Self {
+        self.alerts.retain(|alert| !alert.is_expired(as_of));
+        self
+    }
+
+    pub fn stations_view(&self) -> ZoneStationsView {
+        ZoneStationsView {
+            zone_code: self.zone_code.clone(),
+            current: self.current.clone(),
+            stations: self.stations.clone(),
+        }
+    }
+
+    /// The station's latest reading alongside the zone's observation, or `None` if the station
+    /// does not report for the zone.
+    pub fn station_view(&self, station_id: &str) -> Option<ZoneStationsView> {
+        self.stations.get(station_id).map(|frame| ZoneStationsView {
+            zone_code: self.zone_code.clone(),
+            current: self.current.clone(),
+            stations: BTreeMap::from([(station_id.to_string(), frame.clone())]),
+        })
+    }
+}
+
+/// Station readings of a zone alongside the zone's observation aggregated across its stations.
+#[derive(Debug, Clone, PartialEq, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneStationsView {
+    pub zone_code: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<WeatherFrame>,
+
+    /// Latest reading of each station, keyed by station identifier.
+    pub stations: BTreeMap<String, WeatherFrame>,
 }
 
 // Updates the CQRS view from events as they are committed
@@ -60,23 +146,59 @@ impl View<LocationZone> for WeatherView {
 
         match &event.payload {
             Evt::ZoneSet(zone_id) => {
-                self.zone_code = zone_id.to_string();
+                // a reactivated zone starts afresh rather than serving pre-retirement weather
+                *self = Self::new(zone_id.to_string());
+            },
+
+            Evt::ZoneMetadataUpdated(metadata) => {
+                self.zone = Some(metadata.clone());
             },
 
             Evt::ObservationAdded(frame) => {
                 self.current = Some(*frame.clone());
+                self.current_confirmed_at = None;
+            },
+
+            Evt::ObservationConfirmed(timestamp) => {
+                self.current_confirmed_at = Some(*timestamp);
+            },
+
+            Evt::StationsObserved(stations) => {
+                self.stations = stations.clone();
             },
 
             Evt::ForecastUpdated(forecast) => {
                 self.forecast = forecast.periods.clone();
             },
 
-            Evt::AlertActivated(alert) => {
-                self.alert = Some(alert.clone());
+            Evt::AlertActivated(alert) | Evt::AlertUpdated(alert) => {
+                self.alerts.retain(|a| a.id != alert.id);
+                self.alerts.push(alert.clone());
+                self.alerts.sort_by(WeatherAlert::priority_cmp);
+            },
+
+            Evt::AlertCleared(id) | Evt::AlertExpired(id) => {
+                self.alerts.retain(|a| &a.id != id);
             },
 
             Evt::AlertDeactivated => {
-                self.alert = None;
+                self.alerts.clear();
+            },
+
+            Evt::AlertsConfirmed => {},
+
+            Evt::ZonePaused => {
+                self.paused = true;
+            },
+
+            Evt::ZoneResumed => {
+                self.paused = false;
+            },
+
+            Evt::ZoneWatched(_) | Evt::ZoneUnwatched(_) => {},
+
+            Evt::ZoneRetired => {
+                self.retired = true;
             },
         }
     }
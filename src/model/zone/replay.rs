@@ -0,0 +1,71 @@
+use super::{LocationZoneEvent, WeatherView};
+use crate::model::{LocationZone, LocationZoneCode, VersionedEvent, OCCURRED_AT_METADATA};
+use chrono::{DateTime, Utc};
+use cqrs_es::persist::{
+    EventUpcaster, PersistedEventRepository, PersistenceError, SerializedEvent,
+};
+use cqrs_es::{EventEnvelope, View};
+use postgres_es::PostgresEventRepository;
+use sqlx::PgPool;
+
+/// Rebuilds the zone's weather view as served at the time by replaying the zone's events that
+/// occurred by then. Events recorded before their metadata carried an occurrence time are placed
+/// by the time in their payload, if any, or else by the timed events following them. Returns
+/// `None` if the zone was not yet watched at the time, or its events cannot be placed by then.
+#[tracing::instrument(level = "debug", skip(db_pool))]
+pub async fn replay_weather_view(
+    db_pool: &PgPool, zone: &LocationZoneCode, as_of: DateTime<Utc>,
+) -> Result<Option<WeatherView>, PersistenceError> {
+    let repo = PostgresEventRepository::new(db_pool.clone());
+    let events = repo.get_events::<LocationZone>(zone.as_ref()).await?;
+    let upcasters = LocationZoneEvent::upcasters();
+
+    let mut view: Option<WeatherView> = None;
+    let mut is_placed = false;
+    for event in events {
+        let recorded_at = occurred_at(&event);
+        let event = if upcasters.can_upcast(&event.event_type, &event.event_version) {
+            upcasters.upcast(event)
+        } else {
+            event
+        };
+
+        let envelope = EventEnvelope::<LocationZone>::try_from(event)?;
+        match recorded_at.or_else(|| payload_time(&envelope.payload)) {
+            // events are in sequence, so none after the first occurring past the time are replayed
+            Some(occurred_at) if as_of < occurred_at => break,
+            Some(_) => is_placed = true,
+            None => {},
+        }
+
+        view.get_or_insert_with(|| WeatherView::new(zone.as_ref()))
+            .update(&envelope);
+    }
+
+    // untimed events are only known to precede the time once a later event occurred by then
+    Ok(view.filter(|_| is_placed))
+}
+
+fn occurred_at(event: &SerializedEvent) -> Option<DateTime<Utc>> {
+    event
+        .metadata
+        .get(OCCURRED_AT_METADATA)
+        .and_then(|occurred_at| occurred_at.as_str())
+        .and_then(parse_time)
+}
+
+/// Time the event's payload records, for events recorded without an occurrence time.
+fn payload_time(event: &LocationZoneEvent) -> Option<DateTime<Utc>> {
+    match event {
+        LocationZoneEvent::ObservationAdded(frame) => parse_time(&frame.timestamp.format()),
+        LocationZoneEvent::ObservationConfirmed(timestamp) => parse_time(&timestamp.format()),
+        LocationZoneEvent::ForecastUpdated(forecast) => Some(forecast.updated),
+        _ => None,
+    }
+}
+
+fn parse_time(time: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
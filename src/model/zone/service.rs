@@ -1,27 +1,44 @@
-use crate::model::{LocationZoneCode, LocationZoneType, WeatherFrame, ZoneForecast};
+use crate::model::{
+    LocationZoneCode, LocationZoneType, ObservationTolerances, ZoneForecast, ZoneMetadata,
+    ZoneObservation,
+};
 use crate::services::noaa::{NoaaWeatherError, NoaaWeatherServices, ZoneWeatherApi};
 use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
-pub struct LocationServices(NoaaWeatherServices);
+pub struct LocationServices {
+    noaa: NoaaWeatherServices,
+    observation_tolerances: ObservationTolerances,
+}
 
 impl LocationServices {
-    pub fn new(noaa: NoaaWeatherServices) -> Self {
-        Self(noaa)
+    pub fn new(noaa: NoaaWeatherServices, observation_tolerances: ObservationTolerances) -> Self {
+        Self { noaa, observation_tolerances }
+    }
+
+    /// Tolerances within which a new observation confirms the zone's current observation.
+    pub fn observation_tolerances(&self) -> &ObservationTolerances {
+        &self.observation_tolerances
     }
 }
 
 #[async_trait]
 impl ZoneWeatherApi for LocationServices {
+    async fn zone_metadata(
+        &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
+    ) -> Result<Option<ZoneMetadata>, NoaaWeatherError> {
+        self.noaa.zone_metadata(zone_type, zone_code).await
+    }
+
     async fn zone_observation(
         &self, zone_code: &LocationZoneCode,
-    ) -> Result<WeatherFrame, NoaaWeatherError> {
-        self.0.zone_observation(zone_code).await
+    ) -> Result<ZoneObservation, NoaaWeatherError> {
+        self.noaa.zone_observation(zone_code).await
     }
 
     async fn zone_forecast(
         &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
     ) -> Result<ZoneForecast, NoaaWeatherError> {
-        self.0.zone_forecast(zone_type, zone_code).await
+        self.noaa.zone_forecast(zone_type, zone_code).await
     }
 }
@@ -0,0 +1,74 @@
+use super::location::AGGREGATE_TYPE;
+use super::{LocationZoneAggregate, LocationZoneCommand};
+use crate::model;
+use crate::model::registrar::MONITORED_ZONES_QUERY_VIEW;
+use sql_query_builder as sql;
+use sqlx::PgPool;
+
+/// Brings location zone watchers in step with the watchlists monitoring each zone. Zones recorded
+/// before watchers were tracked are watched by every watchlist holding them, and zones no watchlist
+/// holds are retired. Commands go through the aggregate, so already reconciled zones record
+/// nothing and the step is safe to run at every startup.
+#[tracing::instrument(level = "debug", skip(location_agg, db_pool))]
+pub async fn reconcile_zone_watchers(
+    location_agg: &LocationZoneAggregate, db_pool: &PgPool,
+) -> Result<(), sqlx::Error> {
+    for (watchlist, zone) in find_zone_watchers(db_pool).await? {
+        let outcome = location_agg
+            .execute_with_metadata(
+                &zone,
+                LocationZoneCommand::Watch(watchlist.clone()),
+                model::occurrence_metadata(),
+            )
+            .await;
+        if let Err(error) = outcome {
+            tracing::error!(?error, %zone, %watchlist, "failed to reconcile zone watcher");
+        }
+    }
+
+    for zone in find_unwatched_zones(db_pool).await? {
+        let outcome = location_agg
+            .execute_with_metadata(
+                &zone,
+                LocationZoneCommand::Retire,
+                model::occurrence_metadata(),
+            )
+            .await;
+        if let Err(error) = outcome {
+            tracing::error!(?error, %zone, "failed to retire zone no watchlist holds");
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_zone_watchers(db_pool: &PgPool) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let select_sql = sql::Select::new()
+        .select("monitored.view_id, zones.key")
+        .from(&format!(
+            "{MONITORED_ZONES_QUERY_VIEW} monitored, json_each(monitored.payload -> 'zones') zones"
+        ))
+        .where_clause(&format!(
+            "EXISTS (SELECT 1 FROM events WHERE aggregate_type = '{AGGREGATE_TYPE}' AND \
+             aggregate_id = zones.key)"
+        ))
+        .order_by("zones.key, monitored.view_id")
+        .to_string();
+
+    sqlx::query_as(&select_sql).fetch_all(db_pool).await
+}
+
+async fn find_unwatched_zones(db_pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    let select_sql = sql::Select::new()
+        .select("DISTINCT aggregate_id")
+        .from("events")
+        .where_clause(&format!("aggregate_type = '{AGGREGATE_TYPE}'"))
+        .where_clause(&format!(
+            "NOT EXISTS (SELECT 1 FROM {MONITORED_ZONES_QUERY_VIEW} monitored, \
+             json_each(monitored.payload -> 'zones') zones WHERE zones.key = aggregate_id)"
+        ))
+        .to_string();
+
+    let rows: Vec<(String,)> = sqlx::query_as(&select_sql).fetch_all(db_pool).await?;
+    Ok(rows.into_iter().map(|(zone,)| zone).collect())
+}
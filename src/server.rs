@@ -1,11 +1,15 @@
 mod errors;
 mod health_routes;
+mod requested_by;
 mod result;
 mod state;
+mod watchlist_routes;
 mod weather_routes;
+mod zone_codes;
 
 pub use result::HttpResult;
 
+use crate::model::snapshots::{self, SnapshotError};
 use crate::settings::HttpApiSettings;
 use crate::Settings;
 use std::fmt;
@@ -47,7 +51,7 @@ impl Server {
     #[tracing::instrument(level = "debug", skip(settings))]
     pub async fn build(settings: &Settings) -> Result<Self, ApiError> {
         let connection_pool = get_connection_pool(&settings.database);
-        let app_state = state::make_app_state(connection_pool).await?;
+        let app_state = state::make_app_state(connection_pool, settings).await?;
 
         let address = settings.http_api.server.address();
         let listener = tokio::net::TcpListener::bind(&address).await?;
@@ -77,6 +81,13 @@ impl Server {
     }
 }
 
+/// Rebuilds the aggregate snapshots per the settings, apart from running the server.
+#[tracing::instrument(level = "debug", skip(settings))]
+pub async fn rebuild_snapshots(settings: &Settings) -> Result<(), SnapshotError> {
+    let db_pool = get_connection_pool(&settings.database);
+    snapshots::rebuild_snapshots(&db_pool, &settings.snapshots).await
+}
+
 pub fn get_connection_pool(settings: &DatabaseSettings) -> PgPool {
     let connection_options = settings.pg_connect_options_with_db();
     settings.pg_pool_options().connect_lazy_with(connection_options)
@@ -136,6 +147,7 @@ pub async fn run_http_server(
     let api_routes = Router::new()
         .nest("/health", health_routes::api())
         .nest("/weather", weather_routes::api())
+        .nest("/watchlists", watchlist_routes::api())
         .with_state(state);
 
     let app = Router::new()
@@ -144,6 +156,10 @@ pub async fn run_http_server(
                 SwaggerUrl::with_primary("weather_api", "/api-doc/weather-openapi.json", true),
                 weather_routes::WeatherApiDoc::openapi(),
             ),
+            (
+                SwaggerUrl::new("watchlist_api", "/api-doc/watchlist-openapi.json"),
+                watchlist_routes::WatchlistApiDoc::openapi(),
+            ),
             (
                 SwaggerUrl::new("health_api", "/api-doc/health-openapi.json"),
                 health_routes::HealthApiDoc::openapi(),
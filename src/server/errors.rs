@@ -1,4 +1,6 @@
 use crate::model::registrar::RegistrarError;
+use crate::model::update::UpdateLocationsError;
+use crate::model::zone::LocationZoneError;
 use thiserror::Error;
 use utoipa::ToSchema;
 
@@ -19,9 +21,21 @@ pub enum ApiError {
     #[error("Invalid JSON payload: {0}")]
     Json(#[from] axum::extract::rejection::JsonRejection),
 
+    #[error("Invalid zone codes payload: {0}")]
+    ZoneCodes(String),
+
+    #[error("Invalid query parameter: {0}")]
+    Parameter(String),
+
     #[error("call to location registrar failed: {0}")]
     Registrar(#[from] cqrs_es::AggregateError<RegistrarError>),
 
+    #[error("call to location zone failed: {0}")]
+    LocationZone(#[from] cqrs_es::AggregateError<LocationZoneError>),
+
+    #[error("call to weather update process failed: {0}")]
+    UpdateLocations(#[from] cqrs_es::AggregateError<UpdateLocationsError>),
+
     #[error("HTTP engine error: {0}")]
     HttpEngine(#[from] hyper::Error),
 
@@ -33,6 +47,9 @@ pub enum ApiError {
 
     #[error("failed joining with thread: {0}")]
     Join(#[from] tokio::task::JoinError),
+
+    #[error("invalid weather update schedule: {0}")]
+    UpdateSchedule(#[from] cron::error::Error),
 }
 
 impl From<cqrs_es::persist::PersistenceError> for ApiError {
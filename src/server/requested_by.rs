@@ -0,0 +1,41 @@
+use crate::model::registrar;
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+const REQUESTED_BY_HEADER: &str = "x-requested-by";
+
+const ANONYMOUS: &str = "anonymous";
+
+/// Caller recorded against registrar commands, taken from the `X-Requested-By` header. Requests
+/// without the header are attributed to "anonymous".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestedBy(pub String);
+
+impl RequestedBy {
+    pub fn metadata(&self) -> HashMap<String, String> {
+        registrar::request_metadata(&self.0)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RequestedBy
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let requested_by = parts
+            .headers
+            .get(REQUESTED_BY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(ANONYMOUS);
+
+        Ok(Self(requested_by.to_string()))
+    }
+}
@@ -1,9 +1,13 @@
 use super::errors::ApiError;
+use crate::model::registrar::RegistrarError;
+use crate::model::update::UpdateLocationsError;
+use crate::model::zone::LocationZoneError;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use cqrs_es::AggregateError;
 use serde::Serialize;
 use std::borrow::Cow;
 
@@ -59,6 +63,7 @@ impl From<anyhow::Error> for ErrorReport {
 pub enum HttpError {
     BadRequest { error: ErrorReport },
     NotFound { message: Cow<'static, str> },
+    Conflict { error: ErrorReport },
     Internal { error: ErrorReport },
 }
 
@@ -66,9 +71,64 @@ impl HttpError {
     fn from_error(error: anyhow::Error) -> Self {
         tracing::error!("HTTP handler error: {error}");
         match error.downcast_ref::<ApiError>() {
-            Some(ApiError::Path(_)) => Self::BadRequest { error: error.into() },
+            Some(ApiError::Path(_) | ApiError::ZoneCodes(_) | ApiError::Parameter(_)) => {
+                Self::BadRequest { error: error.into() }
+            },
+            Some(ApiError::Registrar(AggregateError::UserError(
+                RegistrarError::RejectedCommand(_),
+            ))) => Self::BadRequest { error: error.into() },
+            Some(ApiError::Registrar(AggregateError::UserError(
+                RegistrarError::UpdateInProgress(running),
+            ))) => Self::Conflict {
+                error: ErrorReport {
+                    error: format!(
+                        "weather update {running} already in progress for requested zones"
+                    ),
+                    error_code: Some("update_in_progress".to_string()),
+                    backtrace: None,
+                },
+            },
+            Some(ApiError::Registrar(AggregateError::AggregateConflict)) => Self::Conflict {
+                error: ErrorReport {
+                    error: "registrar changed concurrently; retry the request".to_string(),
+                    error_code: Some("registrar_conflict".to_string()),
+                    backtrace: None,
+                },
+            },
+            Some(ApiError::Registrar(AggregateError::UserError(
+                RegistrarError::NothingToUpdate(reason),
+            ))) => Self::Conflict {
+                error: ErrorReport {
+                    error: format!("nothing to update: {reason}"),
+                    error_code: Some("nothing_to_update".to_string()),
+                    backtrace: None,
+                },
+            },
+            Some(ApiError::LocationZone(AggregateError::UserError(
+                LocationZoneError::RejectedCommand(_),
+            ))) => Self::BadRequest { error: error.into() },
+            Some(ApiError::Registrar(AggregateError::UserError(RegistrarError::LocationZone(
+                AggregateError::UserError(LocationZoneError::UnknownZone(zone)),
+            )))) => Self::BadRequest {
+                error: ErrorReport {
+                    error: format!("weather provider does not know zone: {zone}"),
+                    error_code: Some("unknown_zone".to_string()),
+                    backtrace: None,
+                },
+            },
+            Some(ApiError::UpdateLocations(AggregateError::UserError(
+                UpdateLocationsError::RejectedCommand(_),
+            ))) => Self::Conflict {
+                error: ErrorReport {
+                    error: error.to_string(),
+                    error_code: Some("update_not_running".to_string()),
+                    backtrace: None,
+                },
+            },
             Some(
                 ApiError::Registrar(_)
+                | ApiError::LocationZone(_)
+                | ApiError::UpdateLocations(_)
                 | ApiError::ParseUrl(_)
                 | ApiError::Noaa(_)
                 | ApiError::IO(_)
@@ -76,7 +136,8 @@ impl HttpError {
                 | ApiError::HttpEngine(_)
                 | ApiError::Sql(_)
                 | ApiError::Database { .. }
-                | ApiError::Join(_),
+                | ApiError::Join(_)
+                | ApiError::UpdateSchedule(_),
             ) => Self::Internal { error: error.into() },
 
             // Some(BankError::BankAccount(BankAccountError::NotFound(account_id))) => {
@@ -106,6 +167,7 @@ impl IntoResponse for HttpError {
         match self {
             Self::NotFound { message } => (StatusCode::NOT_FOUND, Json(message)).into_response(),
             Self::BadRequest { error } => (StatusCode::BAD_REQUEST, Json(error)).into_response(),
+            Self::Conflict { error } => (StatusCode::CONFLICT, Json(error)).into_response(),
             Self::Internal { error } => {
                 (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response()
             },
@@ -1,16 +1,21 @@
 use super::errors::ApiError;
 use crate::model::registrar::{self, MonitoredZonesViewProjection, RegistrarAggregate};
-use crate::model::update;
-use crate::model::update::UpdateLocationsViewProjection;
-use crate::model::zone::{self, LocationZone, LocationZoneAggregate, WeatherViewProjection};
-use crate::model::{CommandRelay, EventBroadcastQuery, UpdateLocationsSaga};
+use crate::model::scheduler::{
+    UpdateScheduleStatus, UpdateScheduleStatusRef, UpdateWeatherScheduler,
+};
+use crate::model::update::{self, UpdateDeadlineSweeper, UpdateLocationsViewProjection};
+use crate::model::zone::{
+    self, AlertExpirySweeper, LocationZone, LocationZoneAggregate, WeatherViewProjection,
+};
+use crate::model::{CancelledCorrelations, CommandRelay, EventBroadcastQuery, UpdateLocationsSaga};
 use crate::services::noaa::{NoaaWeatherApi, NoaaWeatherServices};
+use crate::Settings;
 use axum::extract::FromRef;
 use sqlx::PgPool;
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
 use url::Url;
 
@@ -22,9 +27,14 @@ pub struct AppState {
     pub weather_view: WeatherViewProjection,
     pub monitored_zones_view: MonitoredZonesViewProjection,
     pub update_locations_view: UpdateLocationsViewProjection,
+    pub update_schedule: UpdateScheduleStatusRef,
     pub db_pool: PgPool,
     pub location_relay_handler: Arc<JoinHandle<()>>,
+    pub registrar_relay_handler: Arc<JoinHandle<()>>,
     pub location_subscriber_handler: Arc<JoinHandle<()>>,
+    pub update_scheduler_handler: Option<Arc<JoinHandle<()>>>,
+    pub alert_sweeper_handler: Option<Arc<JoinHandle<()>>>,
+    pub update_deadline_sweeper_handler: Arc<JoinHandle<()>>,
 }
 
 impl fmt::Debug for AppState {
@@ -69,14 +79,20 @@ impl FromRef<AppState> for UpdateLocationsViewProjection {
     }
 }
 
+impl FromRef<AppState> for UpdateScheduleStatusRef {
+    fn from_ref(app: &AppState) -> Self {
+        app.update_schedule.clone()
+    }
+}
+
 impl FromRef<AppState> for PgPool {
     fn from_ref(app: &AppState) -> Self {
         app.db_pool.clone()
     }
 }
 
-#[tracing::instrument(level = "debug")]
-pub async fn make_app_state(db_pool: PgPool) -> Result<AppState, ApiError> {
+#[tracing::instrument(level = "debug", skip(settings))]
+pub async fn make_app_state(db_pool: PgPool, settings: &Settings) -> Result<AppState, ApiError> {
     let user_agent = axum::http::HeaderValue::from_str("(here.com, contact@example.com)")
         .expect("invalid user_agent");
     let base_url = Url::from_str("https://api.weather.gov")?;
@@ -87,6 +103,9 @@ pub async fn make_app_state(db_pool: PgPool) -> Result<AppState, ApiError> {
     // e.g., location and relay in zone module; and,  update in update module
     let (location_tx, location_rx) = mpsc::channel(num_cpus::get());
     let (update_tx, update_rx) = mpsc::channel(num_cpus::get());
+    let (registrar_tx, registrar_rx) = mpsc::channel(num_cpus::get());
+    let (location_failure_tx, location_failure_rx) = mpsc::channel(num_cpus::get());
+    let cancelled_updates = CancelledCorrelations::default();
 
     let location_broadcast_query: EventBroadcastQuery<LocationZone> =
         EventBroadcastQuery::new(num_cpus::get());
@@ -96,27 +115,82 @@ pub async fn make_app_state(db_pool: PgPool) -> Result<AppState, ApiError> {
     );
 
     let (update_locations_agg, update_locations_view) = update::make_update_locations_saga(
-        location_tx,
+        location_tx.clone(),
         (update_tx, update_rx),
+        registrar_tx.clone(),
+        location_failure_rx,
+        cancelled_updates.clone(),
         &location_subscriber,
         noaa.clone(),
+        settings.update_deadline.timeout,
+        settings.update_retry.clone(),
+        settings.snapshots.update_locations,
         db_pool.clone(),
     )
     .await;
 
-    let (location_agg, weather_view) =
-        zone::make_location_zone_aggregate_view(location_broadcast_query, noaa, db_pool.clone());
+    let (location_agg, weather_view) = zone::make_location_zone_aggregate_view(
+        location_broadcast_query,
+        noaa,
+        settings.observation_history.clone(),
+        settings.observation_change.clone(),
+        settings.snapshots.location_zone,
+        db_pool.clone(),
+    );
+    zone::reconcile_zone_watchers(&location_agg, &db_pool).await?;
 
     let (registrar_agg, monitored_zones_view) = registrar::make_registrar_aggregate(
         db_pool.clone(),
         location_agg.clone(),
+        location_tx,
+        registrar_tx,
         update_locations_agg.clone(),
+        settings.registrar.concurrent_updates,
+        settings.snapshots.registrar,
     );
 
-    let location_relay = CommandRelay::new(location_agg.clone(), location_rx);
+    let registrar_relay = CommandRelay::new(registrar_agg.clone(), registrar_rx);
+    let registrar_relay_handler = Arc::new(registrar_relay.run());
+    let location_relay = CommandRelay::new(location_agg.clone(), location_rx)
+        .with_failure_tx(location_failure_tx)
+        .with_cancellations(cancelled_updates);
     let location_relay_handler = Arc::new(location_relay.run());
     let location_subscriber_handler = Arc::new(location_subscriber.run());
 
+    let (update_schedule, update_scheduler_handler) = if settings.update_scheduler.enabled {
+        let scheduler = UpdateWeatherScheduler::new(
+            &settings.update_scheduler,
+            registrar_agg.clone(),
+            monitored_zones_view.clone(),
+            db_pool.clone(),
+        )?;
+        tracing::info!(?scheduler, "starting weather update scheduler");
+        (scheduler.status(), Some(Arc::new(scheduler.run())))
+    } else {
+        let status = Arc::new(RwLock::new(UpdateScheduleStatus::default()));
+        (status, None)
+    };
+
+    let alert_sweeper_handler = if settings.alert_expiry.enabled {
+        let sweeper = AlertExpirySweeper::new(
+            &settings.alert_expiry,
+            location_agg.clone(),
+            db_pool.clone(),
+        );
+        tracing::info!(?sweeper, "starting alert expiry sweeper");
+        Some(Arc::new(sweeper.run()))
+    } else {
+        None
+    };
+
+    let deadline_sweeper = UpdateDeadlineSweeper::new(
+        &settings.update_deadline,
+        update_locations_agg.clone(),
+        db_pool.clone(),
+    );
+    tracing::info!(?deadline_sweeper, "starting update deadline sweeper");
+    let update_deadline_sweeper_handler = Arc::new(deadline_sweeper.run());
+
     Ok(AppState {
         registrar_agg,
         update_locations_agg,
@@ -124,8 +198,13 @@ pub async fn make_app_state(db_pool: PgPool) -> Result<AppState, ApiError> {
         weather_view,
         monitored_zones_view,
         update_locations_view,
+        update_schedule,
         db_pool,
         location_relay_handler,
+        registrar_relay_handler,
         location_subscriber_handler,
+        update_scheduler_handler,
+        alert_sweeper_handler,
+        update_deadline_sweeper_handler,
     })
 }
@@ -0,0 +1,324 @@
+use super::requested_by::RequestedBy;
+use super::state::AppState;
+use super::weather_routes::{
+    search_update_index, update_request_response, LabelSelectorParams, UpdateWeatherParams,
+    ZoneTypeParams,
+};
+use super::zone_codes::ZoneCodes;
+use crate::model::registrar::{
+    self, MonitoredZone, MonitoredZonesView, MonitoredZonesViewProjection, RegistrarCommand,
+    UpdateInFlight, WatchlistId, ZoneLabel, ZoneRegistration, ZoneRegistrationOutcome,
+    ZoneUpdateSummary,
+};
+use crate::model::update::{UpdateIndexEntry, UpdateIndexPage, UpdateIndexParams, UpdateStatus};
+use crate::model::{LocationZoneCode, RegistrarAggregate};
+use crate::server::errors::ApiError;
+use crate::server::result::OptionalResult;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{routing, Json, Router};
+use sqlx::PgPool;
+use std::collections::BTreeSet;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        serve_watchlists,
+        serve_watchlist_updates,
+        update_watchlist_weather,
+        serve_watchlist_zones,
+        delete_watchlist_zones,
+        add_watchlist_zones,
+        add_watchlist_zone,
+        remove_watchlist_zone,
+        label_watchlist_zone,
+        unlabel_watchlist_zone,
+    ),
+    components(
+        schemas(
+            WatchlistId, LocationZoneCode, MonitoredZonesView, MonitoredZone, ZoneUpdateSummary,
+            UpdateInFlight,
+            ZoneRegistration, ZoneRegistrationOutcome, UpdateIndexPage, UpdateIndexEntry,
+            UpdateStatus, crate::errors::WeatherError, ApiError,
+        )
+    ),
+    tags((name= "watchlist", description = "Weather Watchlist API"))
+)]
+pub struct WatchlistApiDoc;
+
+pub fn api() -> Router<AppState> {
+    Router::new()
+        .route("/", routing::get(serve_watchlists))
+        .route(
+            "/:watchlist_id/updates",
+            routing::get(serve_watchlist_updates).post(update_watchlist_weather),
+        )
+        .route(
+            "/:watchlist_id/zones",
+            routing::get(serve_watchlist_zones)
+                .post(add_watchlist_zones)
+                .delete(delete_watchlist_zones),
+        )
+        .route(
+            "/:watchlist_id/zones/:zone",
+            routing::post(add_watchlist_zone).delete(remove_watchlist_zone),
+        )
+        .route(
+            "/:watchlist_id/zones/:zone/labels",
+            routing::post(label_watchlist_zone).delete(unlabel_watchlist_zone),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    responses(
+        (status = 200, description = "list all watchlists", body = [WatchlistId]),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "trace", skip(db_pool))]
+async fn serve_watchlists(State(db_pool): State<PgPool>) -> impl IntoResponse {
+    registrar::list_watchlists(&db_pool)
+        .await
+        .map_err::<ApiError, _>(|err| err.into())
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{watchlist_id}/updates",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, UpdateIndexParams),
+    responses(
+        (status = 200, description = "Page of the watchlist's update processes matching the search, most recently started first", body = UpdateIndexPage),
+        (status = 400, description = "invalid watchlist identifier, or search ends before it starts"),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "debug", skip(db_pool))]
+async fn serve_watchlist_updates(
+    Path(watchlist): Path<WatchlistId>, Query(params): Query<UpdateIndexParams>,
+    State(db_pool): State<PgPool>,
+) -> Result<Json<UpdateIndexPage>, ApiError> {
+    search_update_index(&db_pool, Some(&watchlist), &params).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/{watchlist_id}/updates",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, UpdateWeatherParams),
+    responses(
+        (status = 200, description = "Initiate weather update of watchlist zones, or join the update in flight for the zones, responding with the update process identifier"),
+        (status = 202, description = "Update queued behind the update in flight for the zones, responding with the update process identifier"),
+        (status = 400, description = "unknown update step or zone not monitored"),
+        (status = 409, description = "Update rejected while an update is in flight for the zones, or with no unsuspended zone or step to update"),
+        (status = "5XX", description = "server error", body = WeatherError),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "debug", skip(reg, view_repo))]
+async fn update_watchlist_weather(
+    Path(watchlist): Path<WatchlistId>, Query(params): Query<UpdateWeatherParams>,
+    requested_by: RequestedBy, State(reg): State<RegistrarAggregate>,
+    State(view_repo): State<MonitoredZonesViewProjection>,
+) -> Result<(StatusCode, String), ApiError> {
+    let request = registrar::request_weather_update(
+        &watchlist,
+        params.scope()?,
+        requested_by.metadata(),
+        &reg,
+        &view_repo,
+    )
+    .await?;
+    Ok(update_request_response(request))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{watchlist_id}/zones",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, LabelSelectorParams),
+    responses(
+        (status = 200, description = "list all zones monitored by watchlist", body = MonitoredZonesView),
+        (status = 404, description = "no watchlist for identifier"),
+    ),
+)]
+#[tracing::instrument(level = "trace", skip(view_repo, db_pool))]
+async fn serve_watchlist_zones(
+    Path(watchlist): Path<WatchlistId>, Query(params): Query<LabelSelectorParams>,
+    State(view_repo): State<MonitoredZonesViewProjection>, State(db_pool): State<PgPool>,
+) -> impl IntoResponse {
+    let selector = params.selector();
+    let view = registrar::load_monitored_zones(&watchlist, &view_repo, &db_pool)
+        .await
+        .map_err::<ApiError, _>(|error| error.into())
+        .map(|v| OptionalResult(v.map(|v| Json(v.select(&selector)))));
+
+    tracing::debug!("view for watchlist[{watchlist}] monitored zones: {view:?}");
+    view
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{watchlist_id}/zones",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId),
+    responses(
+        (status = 200, description = "delete all zones from watchlist"),
+    ),
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn delete_watchlist_zones(
+    Path(watchlist): Path<WatchlistId>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>,
+) -> impl IntoResponse {
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::ClearZoneMonitoring,
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/{watchlist_id}/zones",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, ZoneTypeParams),
+    request_body(
+        content = [String],
+        description = "zone codes as a JSON array, or as a text/csv upload of codes, optionally each followed by its zone type",
+    ),
+    responses(
+        (status = 200, description = "outcome of registering each zone", body = [ZoneRegistration]),
+        (status = 400, description = "unreadable zone codes payload"),
+        (status = 409, description = "registrar changed concurrently; retry the request"),
+        (status = "5XX", description = "registration failed as a whole", body = WeatherError),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg, db_pool))]
+async fn add_watchlist_zones(
+    Path(watchlist): Path<WatchlistId>, Query(params): Query<ZoneTypeParams>,
+    requested_by: RequestedBy, State(reg): State<RegistrarAggregate>,
+    State(db_pool): State<PgPool>, ZoneCodes(zones): ZoneCodes,
+) -> impl IntoResponse {
+    let metadata = requested_by.metadata();
+    let zones = zones.into_iter().map(|zone| zone.or_zone_type(params.zone_type)).collect();
+    registrar::monitor_forecast_zones(&watchlist, zones, metadata, &reg, &db_pool)
+        .await
+        .map_err::<ApiError, _>(|err| err.into())
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{watchlist_id}/zones/{zone_code}",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, LocationZoneCode, ZoneTypeParams),
+    responses(
+        (status = 200, description = "zone added to watchlist"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn add_watchlist_zone(
+    Path((watchlist, zone_code)): Path<(WatchlistId, LocationZoneCode)>,
+    Query(params): Query<ZoneTypeParams>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>,
+) -> impl IntoResponse {
+    let zone_type = params.zone_type_for(&zone_code);
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::MonitorForecastZone(zone_code, zone_type),
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{watchlist_id}/zones/{zone_code}",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, LocationZoneCode),
+    responses(
+        (status = 200, description = "zone removed from watchlist"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn remove_watchlist_zone(
+    Path((watchlist, zone_code)): Path<(WatchlistId, LocationZoneCode)>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>,
+) -> impl IntoResponse {
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::ForgetForecastZone(zone_code),
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/{watchlist_id}/zones/{zone_code}/labels",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, LocationZoneCode),
+    request_body = [String],
+    responses(
+        (status = 200, description = "labels added to watchlist zone"),
+        (status = 400, description = "zone not monitored by watchlist or invalid label"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn label_watchlist_zone(
+    Path((watchlist, zone_code)): Path<(WatchlistId, LocationZoneCode)>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>, Json(labels): Json<BTreeSet<ZoneLabel>>,
+) -> impl IntoResponse {
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::LabelZone(zone_code, labels),
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{watchlist_id}/zones/{zone_code}/labels",
+    context_path = "/api/v1/watchlists",
+    tag = "watchlist",
+    params(WatchlistId, LocationZoneCode),
+    request_body = [String],
+    responses(
+        (status = 200, description = "labels removed from watchlist zone"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn unlabel_watchlist_zone(
+    Path((watchlist, zone_code)): Path<(WatchlistId, LocationZoneCode)>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>, Json(labels): Json<BTreeSet<ZoneLabel>>,
+) -> impl IntoResponse {
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::UnlabelZone(zone_code, labels),
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
@@ -1,35 +1,67 @@
+use super::requested_by::RequestedBy;
 use super::state::AppState;
-use crate::model::registrar::{MonitoredZonesView, MonitoredZonesViewProjection, RegistrarCommand};
+use super::zone_codes::ZoneCodes;
+use crate::model::registrar::{
+    self, LabelSelector, MonitoredZone, MonitoredZonesView, MonitoredZonesViewProjection,
+    RegistrarCommand, UpdateInFlight, UpdateScope, WatchlistId, WeatherUpdateDisposition,
+    WeatherUpdateRequest, ZoneLabel, ZoneRegistration, ZoneRegistrationOutcome, ZoneUpdateSummary,
+};
+use crate::model::scheduler::{UpdateScheduleStatus, UpdateScheduleStatusRef};
 use crate::model::update::{
-    UpdateLocationsEvent, UpdateLocationsState, UpdateLocationsView, UpdateLocationsViewProjection,
+    self, LocationUpdatedStep, LocationUpdatedSteps, UpdateCancellation, UpdateIndexEntry,
+    UpdateIndexPage, UpdateIndexParams, UpdateLocationsCommand, UpdateLocationsEvent,
+    UpdateLocationsSaga, UpdateLocationsState, UpdateLocationsView, UpdateLocationsViewProjection,
+    UpdateStatus,
+};
+use crate::model::zone::{
+    self, LocationZoneCommand, ObservationWindow, WeatherViewProjection, ZoneStationsView,
+};
+use crate::model::{
+    LocationZoneAggregate, LocationZoneCode, LocationZoneType, RegistrarAggregate, WeatherFrame,
 };
-use crate::model::zone::WeatherViewProjection;
-use crate::model::{registrar, LocationZoneCode, RegistrarAggregate};
 use crate::server::errors::ApiError;
 use crate::server::result::OptionalResult;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 use axum::{routing, Json, Router};
+use chrono::{DateTime, Utc};
 use cqrs_es::persist::ViewRepository;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use sqlx::PgPool;
+use std::collections::BTreeSet;
+use std::str::FromStr;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         update_weather,
+        serve_update_schedule,
+        serve_updates,
         serve_update_state,
+        cancel_update,
         serve_location_weather,
+        serve_zone_observations,
+        serve_zone_stations,
+        serve_zone_station,
         serve_all_zones,
         delete_all_zones,
+        add_forecast_zones,
         add_forecast_zone,
         remove_forecast_zone,
+        label_zone,
+        unlabel_zone,
+        pause_zone,
+        resume_zone,
     ),
     components(
         schemas(
-            LocationZoneCode, UpdateLocationsView, MonitoredZonesView,
-            UpdateLocationsEvent, UpdateLocationsState,
+            LocationZoneCode, LocationZoneType, UpdateLocationsView, MonitoredZonesView,
+            MonitoredZone, ZoneUpdateSummary, UpdateInFlight, UpdateLocationsEvent, UpdateLocationsState,
+            UpdateScheduleStatus, WeatherFrame, ZoneStationsView, ZoneRegistration,
+            ZoneRegistrationOutcome, LabelSelector, UpdateCancellation, UpdateIndexPage,
+            UpdateIndexEntry, UpdateStatus,
             crate::errors::WeatherError, ApiError,
         )
     ),
@@ -40,16 +72,35 @@ pub struct WeatherApiDoc;
 pub fn api() -> Router<AppState> {
     Router::new()
         .route("/", routing::post(update_weather))
-        .route("/updates/:update_id", routing::get(serve_update_state))
+        .route("/schedule", routing::get(serve_update_schedule))
+        .route("/updates", routing::get(serve_updates))
+        .route(
+            "/updates/:update_id",
+            routing::get(serve_update_state).delete(cancel_update),
+        )
         .route("/:zone", routing::get(serve_location_weather))
+        .route("/:zone/observations", routing::get(serve_zone_observations))
+        .route("/:zone/stations", routing::get(serve_zone_stations))
+        .route(
+            "/:zone/stations/:station_id",
+            routing::get(serve_zone_station),
+        )
         .route(
             "/zones",
-            routing::get(serve_all_zones).delete(delete_all_zones),
+            routing::get(serve_all_zones)
+                .post(add_forecast_zones)
+                .delete(delete_all_zones),
         )
         .route(
             "/zones/:zone",
             routing::post(add_forecast_zone).delete(remove_forecast_zone),
         )
+        .route(
+            "/zones/:zone/labels",
+            routing::post(label_zone).delete(unlabel_zone),
+        )
+        .route("/zones/:zone/pause", routing::post(pause_zone))
+        .route("/zones/:zone/resume", routing::post(resume_zone))
 }
 
 #[utoipa::path(
@@ -57,21 +108,59 @@ pub fn api() -> Router<AppState> {
     path = "/",
     context_path = "/api/v1/weather",
     tag = "weather",
+    params(UpdateWeatherParams),
     responses(
-        (status = 200, description = "Initiate services update"),
+        (status = 200, description = "Initiate services update, or join the update in flight for the zones, responding with the update process identifier"),
+        (status = 202, description = "Update queued behind the update in flight for the zones, responding with the update process identifier"),
+        (status = 400, description = "unknown update step or zone not monitored"),
+        (status = 409, description = "Update rejected while an update is in flight for the zones, or with no unsuspended zone or step to update"),
         (status = "5XX", description = "server error", body = WeatherError),
     ),
 )]
 #[axum::debug_handler]
-#[tracing::instrument(level = "debug", skip(reg))]
-async fn update_weather(State(reg): State<RegistrarAggregate>) -> impl IntoResponse {
-    let aggregate_id = registrar::singleton_id();
-    tracing::debug!("DMR: registrar singleton id: {aggregate_id:?}");
+#[tracing::instrument(level = "debug", skip(reg, view_repo))]
+async fn update_weather(
+    Query(params): Query<UpdateWeatherParams>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>, State(view_repo): State<MonitoredZonesViewProjection>,
+) -> Result<(StatusCode, String), ApiError> {
+    let watchlist = WatchlistId::default();
+    let request = registrar::request_weather_update(
+        &watchlist,
+        params.scope()?,
+        requested_by.metadata(),
+        &reg,
+        &view_repo,
+    )
+    .await?;
+    Ok(update_request_response(request))
+}
 
-    reg.execute(&aggregate_id.id, RegistrarCommand::UpdateWeather)
-        .await
-        .map_err::<ApiError, _>(|err| err.into())
-        .map(move |()| (StatusCode::OK, aggregate_id.id.to_string()))
+/// Responds with the identifier of the update serving the request; `202 Accepted` if the
+/// request is queued.
+pub(super) fn update_request_response(request: WeatherUpdateRequest) -> (StatusCode, String) {
+    tracing::info!(?request, "weather update requested");
+    let status = match request.disposition {
+        WeatherUpdateDisposition::Started | WeatherUpdateDisposition::Coalesced => StatusCode::OK,
+        WeatherUpdateDisposition::Queued => StatusCode::ACCEPTED,
+    };
+    (status, request.update_id)
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedule",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    responses(
+        (status = 200, description = "state of the scheduled weather updates", body = UpdateScheduleStatus),
+    ),
+)]
+#[axum::debug_handler]
+async fn serve_update_schedule(
+    State(schedule): State<UpdateScheduleStatusRef>,
+) -> impl IntoResponse {
+    let status = schedule.read().await.clone();
+    Json(status)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, IntoParams, ToSchema, Serialize, Deserialize)]
@@ -98,11 +187,142 @@ impl AsRef<str> for UpdateProcessId {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, IntoParams, Deserialize)]
+#[into_params(parameter_in = Query)]
+pub(super) struct ZoneTypeParams {
+    /// Type of the zone, if other than implied by the zone code; e.g., `forecast` or `marine`.
+    #[serde(default, deserialize_with = "deserialize_zone_type")]
+    pub zone_type: Option<LocationZoneType>,
+}
+
+/// Reads the zone type regardless of case, so requests may name it as NWS does.
+fn deserialize_zone_type<'de, D>(deserializer: D) -> Result<Option<LocationZoneType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|zone_type| LocationZoneType::from_str(&zone_type).map_err(de::Error::custom))
+        .transpose()
+}
+
+impl ZoneTypeParams {
+    pub fn zone_type_for(&self, zone_code: &LocationZoneCode) -> LocationZoneType {
+        self.zone_type.unwrap_or_else(|| zone_code.implied_zone_type())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, IntoParams, Deserialize)]
+#[into_params(parameter_in = Query)]
+pub(super) struct AsOfParams {
+    /// Time at which to report the zone's weather as it was served then; current if omitted.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, IntoParams, Deserialize)]
+#[into_params(parameter_in = Query)]
+pub(super) struct LabelSelectorParams {
+    /// Comma-separated labels a zone must all carry to be selected; e.g.,
+    /// `region:mid-atlantic,customer:acme`.
+    pub labels: Option<String>,
+}
+
+impl LabelSelectorParams {
+    pub fn selector(&self) -> LabelSelector {
+        self.labels.as_deref().map(LabelSelector::parse).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, IntoParams, Deserialize)]
+#[into_params(parameter_in = Query)]
+pub(super) struct UpdateWeatherParams {
+    /// Comma-separated labels a zone must all carry to be updated; e.g.,
+    /// `region:mid-atlantic,customer:acme`.
+    pub labels: Option<String>,
+
+    /// Comma-separated monitored zone codes to update; all selected zones if omitted.
+    pub zones: Option<String>,
+
+    /// Comma-separated update steps to run, of `observation`, `forecast` and `alert`; all steps
+    /// if omitted.
+    pub steps: Option<String>,
+}
+
+impl UpdateWeatherParams {
+    pub fn scope(&self) -> Result<UpdateScope, ApiError> {
+        let selector = self.labels.as_deref().map(LabelSelector::parse).unwrap_or_default();
+
+        let zones = self
+            .zones
+            .as_deref()
+            .map(|zones| split_list(zones).map(LocationZoneCode::new).collect());
+
+        let steps = match self.steps.as_deref() {
+            None => LocationUpdatedSteps::all(),
+            Some(steps) => {
+                let steps = split_list(steps)
+                    .map(|step| {
+                        LocationUpdatedStep::from_str(step).map_err(|_| {
+                            ApiError::Parameter(format!("unknown update step: {step}"))
+                        })
+                    })
+                    .collect::<Result<LocationUpdatedSteps, _>>()?;
+                if steps.is_empty() {
+                    LocationUpdatedSteps::all()
+                } else {
+                    steps
+                }
+            },
+        };
+
+        Ok(UpdateScope { selector, zones, steps })
+    }
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
 #[utoipa::path(
     get,
     path = "/updates",
     context_path = "/api/v1/weather",
     tag = "weather",
+    params(UpdateIndexParams),
+    responses(
+        (status = 200, description = "Page of the update processes matching the search, most recently started first", body = UpdateIndexPage),
+        (status = 400, description = "Search ends before it starts"),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "debug", skip(db_pool))]
+async fn serve_updates(
+    Query(params): Query<UpdateIndexParams>, State(db_pool): State<PgPool>,
+) -> Result<Json<UpdateIndexPage>, ApiError> {
+    search_update_index(&db_pool, None, &params).await
+}
+
+/// Serves the page of indexed updates, of the watchlist if given, matching the search.
+pub(super) async fn search_update_index(
+    db_pool: &PgPool, watchlist: Option<&WatchlistId>, params: &UpdateIndexParams,
+) -> Result<Json<UpdateIndexPage>, ApiError> {
+    if let (Some(since), Some(until)) = (params.since, params.until) {
+        if until < since {
+            return Err(ApiError::Parameter(format!(
+                "update search ends before it starts: since {since} until {until}"
+            )));
+        }
+    }
+
+    let page = update::search_updates(db_pool, watchlist, params).await?;
+    tracing::debug!("{} updates found", page.updates.len());
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/updates/{update_process_id}",
+    context_path = "/api/v1/weather",
+    tag = "weather",
     params(UpdateProcessId),
     responses(
         (status = 200, description = "report on update weather process", body = UpdateLocationsView),
@@ -120,25 +340,69 @@ async fn serve_update_state(
         .map(|v| OptionalResult(v.map(Json)))
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, IntoParams, Deserialize)]
+#[into_params(parameter_in = Query)]
+struct CancelUpdateParams {
+    /// Why the update is cancelled.
+    pub reason: Option<String>,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/updates/{update_process_id}",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(UpdateProcessId, CancelUpdateParams),
+    responses(
+        (status = 200, description = "update process cancelled, recording who cancelled it and why"),
+        (status = 404, description = "no update process for identifier"),
+        (status = 409, description = "update process already finished"),
+    ),
+)]
+#[tracing::instrument(level = "debug", skip(saga, view_repo))]
+async fn cancel_update(
+    Path(update_id): Path<UpdateProcessId>, Query(params): Query<CancelUpdateParams>,
+    requested_by: RequestedBy, State(saga): State<UpdateLocationsSaga>,
+    State(view_repo): State<UpdateLocationsViewProjection>,
+) -> Result<Response, ApiError> {
+    if view_repo.load(update_id.as_ref()).await?.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let cancellation = UpdateCancellation {
+        cancelled_by: requested_by.0.clone(),
+        reason: params.reason,
+    };
+    saga.execute_with_metadata(
+        update_id.as_ref(),
+        UpdateLocationsCommand::Cancel(cancellation),
+        requested_by.metadata(),
+    )
+    .await?;
+    Ok(StatusCode::OK.into_response())
+}
+
 #[utoipa::path(
     get,
     path = "/zones",
     context_path = "/api/v1/weather",
     tag = "weather",
+    params(LabelSelectorParams),
     responses(
         (status = 200, description = "list all zones to monitor", body = [MonitoredZonesView])
     ),
 )]
-#[tracing::instrument(level = "trace", skip(view_repo))]
+#[tracing::instrument(level = "trace", skip(view_repo, db_pool))]
 async fn serve_all_zones(
-    State(view_repo): State<MonitoredZonesViewProjection>,
+    Query(params): Query<LabelSelectorParams>,
+    State(view_repo): State<MonitoredZonesViewProjection>, State(db_pool): State<PgPool>,
 ) -> impl IntoResponse {
-    let registrar_id = registrar::singleton_id();
-    let view = view_repo
-        .load(&registrar_id.id)
+    let watchlist = WatchlistId::default();
+    let selector = params.selector();
+    let view = registrar::load_monitored_zones(&watchlist, &view_repo, &db_pool)
         .await
         .map_err::<ApiError, _>(|error| error.into())
-        .map(|v| OptionalResult(v.map(Json)));
+        .map(|v| OptionalResult(v.map(|v| Json(v.select(&selector)))));
 
     tracing::debug!("view for registrar monitored zones: {view:?}");
     view
@@ -154,11 +418,49 @@ async fn serve_all_zones(
     ),
 )]
 #[tracing::instrument(level = "trace", skip(reg))]
-async fn delete_all_zones(State(reg): State<RegistrarAggregate>) -> impl IntoResponse {
-    let aggregate_id = registrar::singleton_id();
-    reg.execute(&aggregate_id.id, RegistrarCommand::ClearZoneMonitoring)
+async fn delete_all_zones(
+    requested_by: RequestedBy, State(reg): State<RegistrarAggregate>,
+) -> impl IntoResponse {
+    let watchlist = WatchlistId::default();
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::ClearZoneMonitoring,
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/zones",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(ZoneTypeParams),
+    request_body(
+        content = [String],
+        description = "zone codes as a JSON array, or as a text/csv upload of codes, optionally each followed by its zone type",
+    ),
+    responses(
+        (status = 200, description = "outcome of registering each zone", body = [ZoneRegistration]),
+        (status = 400, description = "unreadable zone codes payload"),
+        (status = 409, description = "registrar changed concurrently; retry the request"),
+        (status = "5XX", description = "registration failed as a whole", body = WeatherError),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg, db_pool))]
+async fn add_forecast_zones(
+    Query(params): Query<ZoneTypeParams>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>, State(db_pool): State<PgPool>,
+    ZoneCodes(zones): ZoneCodes,
+) -> impl IntoResponse {
+    let watchlist = WatchlistId::default();
+    let metadata = requested_by.metadata();
+    let zones = zones.into_iter().map(|zone| zone.or_zone_type(params.zone_type)).collect();
+    registrar::monitor_forecast_zones(&watchlist, zones, metadata, &reg, &db_pool)
         .await
         .map_err::<ApiError, _>(|err| err.into())
+        .map(Json)
 }
 
 #[utoipa::path(
@@ -166,19 +468,22 @@ async fn delete_all_zones(State(reg): State<RegistrarAggregate>) -> impl IntoRes
     path = "/zones",
     context_path = "/api/v1/weather",
     tag = "weather",
-    params(LocationZoneCode),
+    params(LocationZoneCode, ZoneTypeParams),
     responses(
         (status = 200, description = "zone added to monitor"),
     )
 )]
 #[tracing::instrument(level = "trace", skip(reg))]
 async fn add_forecast_zone(
-    Path(zone_code): Path<LocationZoneCode>, State(reg): State<RegistrarAggregate>,
+    Path(zone_code): Path<LocationZoneCode>, Query(params): Query<ZoneTypeParams>,
+    requested_by: RequestedBy, State(reg): State<RegistrarAggregate>,
 ) -> impl IntoResponse {
-    let aggregate_id = registrar::singleton_id();
-    reg.execute(
-        &aggregate_id.id,
-        RegistrarCommand::MonitorForecastZone(zone_code),
+    let watchlist = WatchlistId::default();
+    let zone_type = params.zone_type_for(&zone_code);
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::MonitorForecastZone(zone_code, zone_type),
+        requested_by.metadata(),
     )
     .await
     .map_err::<ApiError, _>(|err| err.into())
@@ -196,17 +501,124 @@ async fn add_forecast_zone(
 )]
 #[tracing::instrument(level = "trace", skip(reg))]
 async fn remove_forecast_zone(
-    Path(zone_code): Path<LocationZoneCode>, State(reg): State<RegistrarAggregate>,
+    Path(zone_code): Path<LocationZoneCode>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>,
 ) -> impl IntoResponse {
-    let aggregate_id = registrar::singleton_id();
-    reg.execute(
-        &aggregate_id.id,
+    let watchlist = WatchlistId::default();
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
         RegistrarCommand::ForgetForecastZone(zone_code),
+        requested_by.metadata(),
     )
     .await
     .map_err::<ApiError, _>(|err| err.into())
 }
 
+#[utoipa::path(
+    post,
+    path = "/zones/{zone_code}/labels",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(LocationZoneCode),
+    request_body = [String],
+    responses(
+        (status = 200, description = "labels added to zone"),
+        (status = 400, description = "zone not monitored or invalid label"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn label_zone(
+    Path(zone_code): Path<LocationZoneCode>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>, Json(labels): Json<BTreeSet<ZoneLabel>>,
+) -> impl IntoResponse {
+    let watchlist = WatchlistId::default();
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::LabelZone(zone_code, labels),
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/zones/{zone_code}/labels",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(LocationZoneCode),
+    request_body = [String],
+    responses(
+        (status = 200, description = "labels removed from zone"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(reg))]
+async fn unlabel_zone(
+    Path(zone_code): Path<LocationZoneCode>, requested_by: RequestedBy,
+    State(reg): State<RegistrarAggregate>, Json(labels): Json<BTreeSet<ZoneLabel>>,
+) -> impl IntoResponse {
+    let watchlist = WatchlistId::default();
+    reg.execute_with_metadata(
+        watchlist.as_ref(),
+        RegistrarCommand::UnlabelZone(zone_code, labels),
+        requested_by.metadata(),
+    )
+    .await
+    .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/zones/{zone_code}/pause",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(LocationZoneCode),
+    responses(
+        (status = 200, description = "zone updates suspended, keeping its last known weather"),
+        (status = 400, description = "zone not monitored by any watchlist"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(location_agg))]
+async fn pause_zone(
+    Path(zone_code): Path<LocationZoneCode>, requested_by: RequestedBy,
+    State(location_agg): State<LocationZoneAggregate>,
+) -> impl IntoResponse {
+    location_agg
+        .execute_with_metadata(
+            zone_code.as_ref(),
+            LocationZoneCommand::Pause,
+            requested_by.metadata(),
+        )
+        .await
+        .map_err::<ApiError, _>(|err| err.into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/zones/{zone_code}/resume",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(LocationZoneCode),
+    responses(
+        (status = 200, description = "zone updates resumed"),
+        (status = 400, description = "zone not monitored by any watchlist"),
+    )
+)]
+#[tracing::instrument(level = "trace", skip(location_agg))]
+async fn resume_zone(
+    Path(zone_code): Path<LocationZoneCode>, requested_by: RequestedBy,
+    State(location_agg): State<LocationZoneAggregate>,
+) -> impl IntoResponse {
+    location_agg
+        .execute_with_metadata(
+            zone_code.as_ref(),
+            LocationZoneCommand::Resume,
+            requested_by.metadata(),
+        )
+        .await
+        .map_err::<ApiError, _>(|err| err.into())
+}
+
 #[utoipa::path(
     get,
     path = "/",
@@ -214,23 +626,120 @@ async fn remove_forecast_zone(
     tag = "weather",
     params(
         ("zone_code" = String, Path, description = "Zone Code"),
+        AsOfParams,
     ),
     responses(
-    (status = 200, description = "Location Weather Report", body = WeatherView),
+    (status = 200, description = "Location Weather Report, as of the time if given", body = WeatherView),
+    (status = 404, description = "No location zone found, or not yet watched as of the time, or its history does not reach back to the time"),
+    (status = 410, description = "Location zone is no longer monitored"),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "debug", skip(view_repo, db_pool))]
+async fn serve_location_weather(
+    Path(zone_code): Path<LocationZoneCode>, Query(params): Query<AsOfParams>,
+    State(view_repo): State<WeatherViewProjection>, State(db_pool): State<PgPool>,
+) -> Result<Response, ApiError> {
+    let (view, as_of) = match params.as_of {
+        Some(as_of) => (
+            zone::replay_weather_view(&db_pool, &zone_code, as_of).await?,
+            as_of,
+        ),
+        None => (view_repo.load(zone_code.as_ref()).await?, Utc::now()),
+    };
+
+    tracing::debug!("view for code[{zone_code}] as of {as_of}: {view:?}");
+    let response = match view {
+        Some(view) if view.retired => StatusCode::GONE.into_response(),
+        v => {
+            let v = v.map(|view| Json(view.without_expired_alerts(as_of)));
+            OptionalResult(v).into_response()
+        },
+    };
+    Ok(response)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{zone_code}/observations",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(
+        ("zone_code" = String, Path, description = "Zone Code"),
+        ObservationWindow,
+    ),
+    responses(
+    (status = 200, description = "Observations of the zone within the window, most recent first", body = [WeatherFrame]),
+    (status = 400, description = "Window ends before it starts"),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "debug", skip(db_pool))]
+async fn serve_zone_observations(
+    Path(zone_code): Path<LocationZoneCode>, Query(window): Query<ObservationWindow>,
+    State(db_pool): State<PgPool>,
+) -> Result<Json<Vec<WeatherFrame>>, ApiError> {
+    if let (Some(from), Some(to)) = (window.from, window.to) {
+        if to < from {
+            return Err(ApiError::Parameter(format!(
+                "observation window ends before it starts: from {from} to {to}"
+            )));
+        }
+    }
+
+    let observations = zone::load_observations(&db_pool, &zone_code, &window).await?;
+    tracing::debug!("{} observations for code[{zone_code}]", observations.len());
+    Ok(Json(observations))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{zone_code}/stations",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(
+        ("zone_code" = String, Path, description = "Zone Code"),
+    ),
+    responses(
+    (status = 200, description = "Latest reading of each station observing the zone, with the zone observation", body = ZoneStationsView),
     (status = 404, description = "No location zone found"),
     ),
 )]
 #[axum::debug_handler]
 #[tracing::instrument(level = "debug", skip(view_repo))]
-async fn serve_location_weather(
+async fn serve_zone_stations(
     Path(zone_code): Path<LocationZoneCode>, State(view_repo): State<WeatherViewProjection>,
 ) -> impl IntoResponse {
-    let view = view_repo
+    view_repo
         .load(zone_code.as_ref())
         .await
         .map_err::<ApiError, _>(|err| err.into())
-        .map(|v| OptionalResult(v.map(Json)));
+        .map(|v| OptionalResult(v.map(|view| Json(view.stations_view()))))
+}
 
-    tracing::debug!("view for code[{zone_code}]: {view:?}");
-    view
+#[utoipa::path(
+    get,
+    path = "/{zone_code}/stations/{station_id}",
+    context_path = "/api/v1/weather",
+    tag = "weather",
+    params(
+        ("zone_code" = String, Path, description = "Zone Code"),
+        ("station_id" = String, Path, description = "Observation station identifier, e.g., KSEA"),
+    ),
+    responses(
+    (status = 200, description = "Latest reading of the station, with the zone observation", body = ZoneStationsView),
+    (status = 404, description = "No location zone found or station does not observe the zone"),
+    ),
+)]
+#[axum::debug_handler]
+#[tracing::instrument(level = "debug", skip(view_repo))]
+async fn serve_zone_station(
+    Path((zone_code, station_id)): Path<(LocationZoneCode, String)>,
+    State(view_repo): State<WeatherViewProjection>,
+) -> impl IntoResponse {
+    view_repo
+        .load(zone_code.as_ref())
+        .await
+        .map_err::<ApiError, _>(|err| err.into())
+        .map(|v| OptionalResult(v.and_then(|view| view.station_view(&station_id)).map(Json)))
 }
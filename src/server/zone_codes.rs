@@ -0,0 +1,139 @@
+use crate::model::registrar::RequestedZone;
+use crate::model::LocationZoneType;
+use crate::server::errors::ApiError;
+use async_trait::async_trait;
+use axum::body::HttpBody;
+use axum::extract::FromRequest;
+use axum::http::{header, Request};
+use axum::BoxError;
+use std::str::FromStr;
+
+/// Zone codes submitted for bulk registration, either as a JSON array of codes or as a CSV
+/// upload (`Content-Type: text/csv`) listing codes by row or column. A CSV row of a code followed
+/// by a zone type, e.g. `MDC031,county`, registers the zone as that type. A leading header row
+/// starting with `zone`, `zone_code` or `code` is skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneCodes(pub Vec<RequestedZone>);
+
+const CSV_HEADERS: [&str; 3] = ["zone", "zone_code", "code"];
+
+impl ZoneCodes {
+    fn from_json(body: &str) -> Result<Self, ApiError> {
+        serde_json::from_str::<Vec<String>>(body)
+            .map(|codes| {
+                Self(codes.into_iter().map(|code| RequestedZone::new(code, None)).collect())
+            })
+            .map_err(|err| ApiError::ZoneCodes(err.to_string()))
+    }
+
+    fn from_csv(body: &str) -> Self {
+        let mut rows = body
+            .lines()
+            .map(|line| {
+                line.split(',')
+                    .map(|cell| cell.trim().trim_matches('"').trim())
+                    .filter(|cell| !cell.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cells| !cells.is_empty())
+            .peekable();
+
+        if let Some(header) = rows.peek() {
+            if CSV_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(header[0])) {
+                rows.next();
+            }
+        }
+
+        let zones = rows
+            .flat_map(|cells| {
+                let zone_type = match cells.as_slice() {
+                    [_, zone_type] => LocationZoneType::from_str(zone_type).ok(),
+                    _ => None,
+                };
+
+                match zone_type {
+                    Some(zone_type) => vec![RequestedZone::new(cells[0], Some(zone_type))],
+                    None => cells.iter().map(|code| RequestedZone::new(*code, None)).collect(),
+                }
+            })
+            .collect();
+
+        Self(zones)
+    }
+}
+
+#[async_trait]
+impl<S, B> FromRequest<S, B> for ZoneCodes
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let is_csv = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map_or(false, |content_type| content_type.starts_with("text/csv"));
+
+        let body = String::from_request(req, state)
+            .await
+            .map_err(|rejection| ApiError::ZoneCodes(rejection.to_string()))?;
+
+        if is_csv {
+            Ok(Self::from_csv(&body))
+        } else {
+            Self::from_json(&body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_zone_codes_from_json() {
+        let actual = ZoneCodes::from_json(r#"["WAZ558", "WAC033"]"#).unwrap();
+        assert_eq!(
+            actual,
+            ZoneCodes(vec![
+                RequestedZone::new("WAZ558", None),
+                RequestedZone::new("WAC033", None),
+            ])
+        );
+        assert!(ZoneCodes::from_json(r#"{"zone": "WAZ558"}"#).is_err());
+    }
+
+    #[test]
+    fn test_zone_codes_from_csv() {
+        let actual = ZoneCodes::from_csv("zone_code\nWAZ558\n\n\"WAC033\"\r\nORZ006, IDZ001\n");
+        assert_eq!(
+            actual,
+            ZoneCodes(vec![
+                RequestedZone::new("WAZ558", None),
+                RequestedZone::new("WAC033", None),
+                RequestedZone::new("ORZ006", None),
+                RequestedZone::new("IDZ001", None),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_zone_codes_from_csv_with_zone_types() {
+        let actual =
+            ZoneCodes::from_csv("zone_code,zone_type\nMDC031,county\nMDZ009, Public\nWAZ558\n");
+        assert_eq!(
+            actual,
+            ZoneCodes(vec![
+                RequestedZone::new("MDC031", Some(LocationZoneType::County)),
+                RequestedZone::new("MDZ009", Some(LocationZoneType::Public)),
+                RequestedZone::new("WAZ558", None),
+            ])
+        );
+    }
+}
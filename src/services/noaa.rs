@@ -2,11 +2,13 @@ use crate::errors::WeatherError;
 use crate::model;
 use crate::model::{
     transpose_result, LocationZoneCode, LocationZoneType, WeatherAlert, WeatherFrame, ZoneForecast,
+    ZoneMetadata, ZoneObservation,
 };
 use async_trait::async_trait;
 use chrono::Utc;
 use geojson::{Feature, FeatureCollection, GeoJson};
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::StatusCode;
 use reqwest_middleware::ClientWithMiddleware;
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use std::convert::TryFrom;
@@ -17,9 +19,16 @@ use url::Url;
 
 #[async_trait]
 pub trait ZoneWeatherApi: Send + Sync {
+    /// Describes the zone, or `None` if the weather provider does not know the zone.
+    async fn zone_metadata(
+        &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
+    ) -> Result<Option<ZoneMetadata>, NoaaWeatherError>;
+
+    /// Observes the zone's weather, as aggregated across the zone's stations and as read by each
+    /// station.
     async fn zone_observation(
         &self, zone_code: &LocationZoneCode,
-    ) -> Result<WeatherFrame, NoaaWeatherError>;
+    ) -> Result<ZoneObservation, NoaaWeatherError>;
 
     async fn zone_forecast(
         &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
@@ -39,9 +48,18 @@ pub enum NoaaWeatherServices {
 
 #[async_trait]
 impl ZoneWeatherApi for NoaaWeatherServices {
+    async fn zone_metadata(
+        &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
+    ) -> Result<Option<ZoneMetadata>, NoaaWeatherError> {
+        match self {
+            Self::Noaa(svc) => svc.zone_metadata(zone_type, zone_code).await,
+            Self::HappyPath(svc) => svc.zone_metadata(zone_type, zone_code).await,
+        }
+    }
+
     async fn zone_observation(
         &self, zone_code: &LocationZoneCode,
-    ) -> Result<WeatherFrame, NoaaWeatherError> {
+    ) -> Result<ZoneObservation, NoaaWeatherError> {
         match self {
             Self::Noaa(svc) => svc.zone_observation(zone_code).await,
             Self::HappyPath(svc) => svc.zone_observation(zone_code).await,
@@ -79,6 +97,9 @@ pub enum NoaaWeatherError {
     #[error("error occurred in HTTP middleware calling Weather API: {0}")]
     HttpMiddleware(#[from] reqwest_middleware::Error),
 
+    #[error("Weather API responded with {status}: {body}")]
+    HttpStatus { status: StatusCode, body: String },
+
     #[error("failed to parse Weather API GeoJson response: {0}")]
     GeoJson(#[from] geojson::Error),
 
@@ -86,6 +107,18 @@ pub enum NoaaWeatherError {
     Weather(#[from] WeatherError),
 }
 
+impl NoaaWeatherError {
+    /// HTTP status of the Weather API's response to the failed call, if the API responded.
+    pub fn http_status(&self) -> Option<StatusCode> {
+        match self {
+            Self::HttpStatus { status, .. } => Some(*status),
+            Self::HttpRequest(error) => error.status(),
+            Self::HttpMiddleware(reqwest_middleware::Error::Reqwest(error)) => error.status(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NoaaWeatherApi {
     client: ClientWithMiddleware,
@@ -136,6 +169,9 @@ impl NoaaWeatherApi {
         let status_code = response.status();
         let body = response.text().await?;
         tracing::debug!(%body, ?status_code, %url, "{label} response body");
+        if !status_code.is_success() {
+            return Err(NoaaWeatherError::HttpStatus { status: status_code, body });
+        }
 
         let geojson = body.parse()?;
         Ok(geojson)
@@ -144,15 +180,41 @@ impl NoaaWeatherApi {
 
 #[async_trait]
 impl ZoneWeatherApi for NoaaWeatherApi {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn zone_metadata(
+        &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
+    ) -> Result<Option<ZoneMetadata>, NoaaWeatherError> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .unwrap()
+            .push("zones")
+            .push(zone_type.nws_path_segment())
+            .push(zone_code.as_ref());
+
+        let response = self.client.get(url.clone()).send().await?;
+        log_response("zone_metadata", &url, &response);
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body = response.error_for_status()?.text().await?;
+        tracing::debug!(%body, %url, "zone_metadata response body");
+
+        let geojson: GeoJson = body.parse()?;
+        let feature = Feature::try_from(geojson)?;
+        Ok(Some(ZoneMetadata::from_feature(zone_type, &feature)?))
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn zone_observation(
         &self, zone: &LocationZoneCode,
-    ) -> Result<WeatherFrame, NoaaWeatherError> {
+    ) -> Result<ZoneObservation, NoaaWeatherError> {
+        // NWS serves zone observations only under forecast zones, whatever the zone's type
         let mut url = self.base_url.clone();
         url.path_segments_mut()
             .unwrap()
             .push("zones")
-            .push("forecast")
+            .push(LocationZoneType::Forecast.nws_path_segment())
             .push(zone.as_ref())
             .push("observations");
 
@@ -169,7 +231,7 @@ impl ZoneWeatherApi for NoaaWeatherApi {
         url.path_segments_mut()
             .unwrap()
             .push("zones")
-            .push(zone_type.into())
+            .push(zone_type.nws_path_segment())
             .push(zone_code.as_ref())
             .push("forecast");
 
@@ -210,10 +272,25 @@ pub struct HappyPathWeatherServices;
 
 #[async_trait]
 impl ZoneWeatherApi for HappyPathWeatherServices {
+    async fn zone_metadata(
+        &self, zone_type: LocationZoneType, zone_code: &LocationZoneCode,
+    ) -> Result<Option<ZoneMetadata>, NoaaWeatherError> {
+        if !zone_code.is_well_formed() {
+            return Ok(None);
+        }
+
+        Ok(Some(ZoneMetadata {
+            zone_code: zone_code.clone(),
+            zone_type,
+            name: "Happy Path".to_string(),
+            state: Some("WA".to_string()),
+        }))
+    }
+
     async fn zone_observation(
         &self, _zone: &LocationZoneCode,
-    ) -> Result<WeatherFrame, NoaaWeatherError> {
-        Ok(WeatherFrame {
+    ) -> Result<ZoneObservation, NoaaWeatherError> {
+        let frame = WeatherFrame {
             timestamp: iso8601_timestamp::Timestamp::now_utc(),
             temperature: Some(model::QuantitativeValue {
                 value: 72.0,
@@ -243,7 +320,10 @@ impl ZoneWeatherApi for HappyPathWeatherServices {
             relative_humidity: None,
             wind_chill: None,
             heat_index: None,
-        })
+        };
+
+        let stations = std::iter::once(("KHPP".to_string(), frame.clone())).collect();
+        Ok(ZoneObservation { frame, stations })
     }
 
     async fn zone_forecast(
@@ -265,6 +345,7 @@ impl AlertApi for HappyPathWeatherServices {
     async fn active_alerts(&self) -> Result<Vec<WeatherAlert>, NoaaWeatherError> {
         Ok(vec![
             WeatherAlert {
+                id: "urn:oid:2.49.0.1.840.0.happy-path.001.1".to_string(),
                 affected_zones: vec![
                     LocationZoneCode::new("MDC031".to_string())
                 ],
@@ -1,10 +1,26 @@
+mod alert_expiry_settings;
 mod cli_options;
 mod http_api_settings;
+mod observation_change_settings;
+mod observation_history_settings;
+mod registrar_settings;
+mod snapshot_settings;
 #[cfg(test)]
 mod tests;
+mod update_deadline_settings;
+mod update_retry_settings;
+mod update_scheduler_settings;
 
+pub use alert_expiry_settings::AlertExpirySettings;
 pub use cli_options::CliOptions;
 pub use http_api_settings::HttpApiSettings;
+pub use observation_change_settings::ObservationChangeSettings;
+pub use observation_history_settings::ObservationHistorySettings;
+pub use registrar_settings::{ConcurrentUpdatePolicy, RegistrarSettings};
+pub use snapshot_settings::SnapshotSettings;
+pub use update_deadline_settings::UpdateDeadlineSettings;
+pub use update_retry_settings::UpdateRetrySettings;
+pub use update_scheduler_settings::UpdateSchedulerSettings;
 
 use serde::Deserialize;
 use settings_loader::{common::database::DatabaseSettings, SettingsLoader};
@@ -14,6 +30,30 @@ pub struct Settings {
     pub http_api: HttpApiSettings,
     pub database: DatabaseSettings,
 
+    #[serde(default)]
+    pub update_scheduler: UpdateSchedulerSettings,
+
+    #[serde(default)]
+    pub update_deadline: UpdateDeadlineSettings,
+
+    #[serde(default)]
+    pub update_retry: UpdateRetrySettings,
+
+    #[serde(default)]
+    pub registrar: RegistrarSettings,
+
+    #[serde(default)]
+    pub observation_history: ObservationHistorySettings,
+
+    #[serde(default)]
+    pub observation_change: ObservationChangeSettings,
+
+    #[serde(default)]
+    pub alert_expiry: AlertExpirySettings,
+
+    #[serde(default)]
+    pub snapshots: SnapshotSettings,
+
     #[serde(flatten)]
     pub correlation: CorrelationSettings,
 }
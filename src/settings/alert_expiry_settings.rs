@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use serde_with::serde_as;
+use std::time::Duration;
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AlertExpirySettings {
+    /// Turn on the background sweeper that expires zone alerts once their expiry or end time
+    /// passes, between weather updates. Default is enabled.
+    #[serde(default = "AlertExpirySettings::default_enabled")]
+    pub enabled: bool,
+
+    /// Time between sweeps for expired alerts.
+    #[serde(
+        alias = "interval_secs",
+        default = "AlertExpirySettings::default_interval"
+    )]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub interval: Duration,
+}
+
+impl AlertExpirySettings {
+    const fn default_enabled() -> bool {
+        true
+    }
+
+    const fn default_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+}
+
+impl Default for AlertExpirySettings {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            interval: Self::default_interval(),
+        }
+    }
+}
@@ -42,6 +42,12 @@ pub struct CliOptions {
     /// Optionally override the engine.node_id setting.
     #[clap(short, long, value_name = "[0, 31)")]
     pub node_id: Option<i8>,
+
+    /// Rebuild the snapshots of the aggregate types snapshotted per the snapshot settings by
+    /// replaying their event streams, then exit without starting the server. Use after an
+    /// aggregate's state changes shape or its snapshot interval changes.
+    #[clap(long)]
+    pub rebuild_snapshots: bool,
 }
 
 const DEFAULT_SEARCH_PATH: &str = "./resources";
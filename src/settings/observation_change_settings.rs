@@ -0,0 +1,11 @@
+use crate::model::ObservationTolerances;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct ObservationChangeSettings {
+    /// Largest change per quantitative property, e.g., `Temperature: 0.5`, for which a new
+    /// observation only confirms the zone's current observation rather than being recorded.
+    /// Properties not listed must match exactly.
+    #[serde(default)]
+    pub tolerances: ObservationTolerances,
+}
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use serde_with::serde_as;
+use std::time::Duration;
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ObservationHistorySettings {
+    /// Observations older than this are dropped from the observation history as new
+    /// observations are recorded. Default is 30 days; unset keeps observations regardless of age.
+    #[serde(
+        alias = "max_age_secs",
+        default = "ObservationHistorySettings::default_max_age"
+    )]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    pub max_age: Option<Duration>,
+
+    /// Most observations kept per zone, dropping the oldest first. Default is unbounded.
+    #[serde(default)]
+    pub max_frames_per_zone: Option<u32>,
+}
+
+impl ObservationHistorySettings {
+    const fn default_max_age() -> Option<Duration> {
+        Some(Duration::from_secs(30 * 24 * 60 * 60))
+    }
+}
+
+impl Default for ObservationHistorySettings {
+    fn default() -> Self {
+        Self {
+            max_age: Self::default_max_age(),
+            max_frames_per_zone: None,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct RegistrarSettings {
+    /// How a watchlist registrar handles a weather update request while an earlier update it
+    /// started is still updating any of the same zones. Default is to coalesce.
+    #[serde(default)]
+    pub concurrent_updates: ConcurrentUpdatePolicy,
+}
+
+/// Policy for a weather update request overlapping an update still in flight.
+#[derive(Debug, Default, Display, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ConcurrentUpdatePolicy {
+    /// Reject the request; e.g., with `409 Conflict`.
+    Reject,
+
+    /// Fold the request into the running update and respond with its identifier.
+    #[default]
+    Coalesce,
+
+    /// Hold the request until the overlapping updates finish, then start it.
+    Queue,
+}
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+
+/// Number of events committed between snapshots of each aggregate type. An aggregate type without
+/// an interval is not snapshotted, so each command replays the aggregate's full event stream.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SnapshotSettings {
+    /// Events between snapshots of a location zone. Default is 100.
+    #[serde(default = "SnapshotSettings::default_location_zone")]
+    pub location_zone: Option<usize>,
+
+    /// Events between snapshots of a watchlist registrar. Default is 100.
+    #[serde(default = "SnapshotSettings::default_registrar")]
+    pub registrar: Option<usize>,
+
+    /// Events between snapshots of an update saga. Sagas are short-lived, so default is none.
+    #[serde(default)]
+    pub update_locations: Option<usize>,
+}
+
+impl SnapshotSettings {
+    const fn default_location_zone() -> Option<usize> {
+        Some(100)
+    }
+
+    const fn default_registrar() -> Option<usize> {
+        Some(100)
+    }
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self {
+            location_zone: Self::default_location_zone(),
+            registrar: Self::default_registrar(),
+            update_locations: None,
+        }
+    }
+}
@@ -31,6 +31,19 @@ mod loading {
             idle_timeout: Some(Duration::from_secs(300)),
             max_lifetime: Some(Duration::from_secs(1_800)),
         },
+        update_scheduler: UpdateSchedulerSettings {
+            enabled: true,
+            interval: Duration::from_secs(900),
+            jitter: Duration::from_secs(30),
+            ..UpdateSchedulerSettings::default()
+        },
+        update_deadline: UpdateDeadlineSettings::default(),
+        update_retry: UpdateRetrySettings::default(),
+        registrar: RegistrarSettings::default(),
+        observation_history: ObservationHistorySettings::default(),
+        observation_change: ObservationChangeSettings::default(),
+        alert_expiry: AlertExpirySettings::default(),
+        snapshots: SnapshotSettings::default(),
         correlation: CorrelationSettings::default(),
     });
 
@@ -80,6 +93,14 @@ mod loading {
                 idle_timeout: Some(Duration::from_secs(300)),
                 max_lifetime: None,
             },
+            update_scheduler: UpdateSchedulerSettings::default(),
+            update_deadline: UpdateDeadlineSettings::default(),
+            update_retry: UpdateRetrySettings::default(),
+            registrar: RegistrarSettings::default(),
+            observation_history: ObservationHistorySettings::default(),
+            observation_change: ObservationChangeSettings::default(),
+            alert_expiry: AlertExpirySettings::default(),
+            snapshots: SnapshotSettings::default(),
             correlation: CorrelationSettings { machine_id: 1, node_id: 1 },
         };
 
@@ -87,6 +108,152 @@ mod loading {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_update_scheduler_settings_serde() {
+        let yaml = r##"|---
+            |enabled: true
+            |cron: "0 */5 * * * *"
+            |jitter_secs: 10
+            |skip_if_running: false
+            |"##
+        .trim_margin()
+        .unwrap();
+
+        let actual: UpdateSchedulerSettings = assert_ok!(serde_yaml::from_str(&yaml));
+        assert_eq!(
+            actual,
+            UpdateSchedulerSettings {
+                enabled: true,
+                cron: Some("0 */5 * * * *".to_string()),
+                interval: Duration::from_secs(15 * 60),
+                jitter: Duration::from_secs(10),
+                skip_if_running: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_registrar_settings_serde() {
+        let actual: RegistrarSettings =
+            assert_ok!(serde_yaml::from_str("concurrent_updates: queue"));
+        assert_eq!(
+            actual,
+            RegistrarSettings { concurrent_updates: ConcurrentUpdatePolicy::Queue }
+        );
+
+        let actual: RegistrarSettings = assert_ok!(serde_yaml::from_str("{}"));
+        assert_eq!(actual.concurrent_updates, ConcurrentUpdatePolicy::Coalesce);
+    }
+
+    #[test]
+    fn test_observation_history_settings_serde() {
+        let actual: ObservationHistorySettings = assert_ok!(serde_yaml::from_str(
+            "max_age_secs: 86400\nmax_frames_per_zone: 500"
+        ));
+        assert_eq!(
+            actual,
+            ObservationHistorySettings {
+                max_age: Some(Duration::from_secs(86_400)),
+                max_frames_per_zone: Some(500),
+            }
+        );
+
+        let actual: ObservationHistorySettings = assert_ok!(serde_yaml::from_str("max_age: ~"));
+        assert_eq!(actual.max_age, None);
+
+        let actual: ObservationHistorySettings = assert_ok!(serde_yaml::from_str("{}"));
+        assert_eq!(actual, ObservationHistorySettings::default());
+    }
+
+    #[test]
+    fn test_observation_change_settings_serde() {
+        use crate::model::{ObservationTolerances, QuantitativeProperty};
+
+        let actual: ObservationChangeSettings = assert_ok!(serde_yaml::from_str(
+            "tolerances:\n  Temperature: 0.5\n  WindSpeed: 2.0"
+        ));
+        assert_eq!(
+            actual.tolerances,
+            ObservationTolerances::new([
+                (QuantitativeProperty::Temperature, 0.5),
+                (QuantitativeProperty::WindSpeed, 2.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_snapshot_settings_serde() {
+        let actual: SnapshotSettings = assert_ok!(serde_yaml::from_str(
+            "location_zone: 50\nregistrar: ~\nupdate_locations: 20"
+        ));
+        assert_eq!(
+            actual,
+            SnapshotSettings {
+                location_zone: Some(50),
+                registrar: None,
+                update_locations: Some(20),
+            }
+        );
+
+        let actual: SnapshotSettings = assert_ok!(serde_yaml::from_str("{}"));
+        assert_eq!(actual, SnapshotSettings::default());
+    }
+
+    #[test]
+    fn test_update_deadline_settings_serde() {
+        let actual: UpdateDeadlineSettings = assert_ok!(serde_yaml::from_str(
+            "timeout_secs: 120\ncheck_interval_secs: 10"
+        ));
+        assert_eq!(
+            actual,
+            UpdateDeadlineSettings {
+                timeout: Duration::from_secs(120),
+                check_interval: Duration::from_secs(10),
+            }
+        );
+
+        let actual: UpdateDeadlineSettings = assert_ok!(serde_yaml::from_str("{}"));
+        assert_eq!(actual, UpdateDeadlineSettings::default());
+    }
+
+    #[test]
+    fn test_update_retry_settings_serde() {
+        let yaml = r##"|---
+            |max_attempts: 5
+            |initial_backoff_secs: 1
+            |backoff_multiplier: 3
+            |max_backoff_secs: 20
+            |"##
+        .trim_margin()
+        .unwrap();
+
+        let actual: UpdateRetrySettings = assert_ok!(serde_yaml::from_str(&yaml));
+        assert_eq!(
+            actual,
+            UpdateRetrySettings {
+                max_attempts: 5,
+                initial_backoff: Duration::from_secs(1),
+                backoff_multiplier: 3,
+                max_backoff: Duration::from_secs(20),
+            }
+        );
+
+        let backoffs: Vec<_> = (1..=5).map(|attempt| actual.backoff(attempt)).collect();
+        assert_eq!(
+            backoffs,
+            vec![
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(3)),
+                Some(Duration::from_secs(9)),
+                Some(Duration::from_secs(20)),
+                None,
+            ]
+        );
+
+        let actual: UpdateRetrySettings = assert_ok!(serde_yaml::from_str("{}"));
+        assert_eq!(actual, UpdateRetrySettings::default());
+    }
+
     #[test]
     fn test_basic_load() {
         let c = assert_ok!(config::Config::builder()
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use serde_with::serde_as;
+use std::time::Duration;
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UpdateDeadlineSettings {
+    /// Time an update saga has to finish its zones before it times out, failing the zones yet to
+    /// finish. Default is 5 minutes.
+    #[serde(alias = "timeout_secs", default = "UpdateDeadlineSettings::default_timeout")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub timeout: Duration,
+
+    /// Time between checks for update sagas past their deadline. Default is 30 seconds.
+    #[serde(
+        alias = "check_interval_secs",
+        default = "UpdateDeadlineSettings::default_check_interval"
+    )]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub check_interval: Duration,
+}
+
+impl UpdateDeadlineSettings {
+    const fn default_timeout() -> Duration {
+        Duration::from_secs(5 * 60)
+    }
+
+    const fn default_check_interval() -> Duration {
+        Duration::from_secs(30)
+    }
+}
+
+impl Default for UpdateDeadlineSettings {
+    fn default() -> Self {
+        Self {
+            timeout: Self::default_timeout(),
+            check_interval: Self::default_check_interval(),
+        }
+    }
+}
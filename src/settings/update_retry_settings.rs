@@ -0,0 +1,78 @@
+use serde::Deserialize;
+use serde_with::serde_as;
+use std::time::Duration;
+
+/// Retries of a zone's update step that failed during an update saga. The wait before each retry
+/// grows exponentially from the initial backoff by the multiplier, up to the max backoff.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UpdateRetrySettings {
+    /// Attempts of a zone's update step, including the first, before the zone fails the update.
+    /// Default is 3.
+    #[serde(default = "UpdateRetrySettings::default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Wait before the first retry of a failed step. Default is 2 seconds.
+    #[serde(
+        alias = "initial_backoff_secs",
+        default = "UpdateRetrySettings::default_initial_backoff"
+    )]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub initial_backoff: Duration,
+
+    /// Factor the wait grows by with each further retry. Default is 2.
+    #[serde(default = "UpdateRetrySettings::default_backoff_multiplier")]
+    pub backoff_multiplier: u32,
+
+    /// Longest wait before a retry. Default is 1 minute.
+    #[serde(
+        alias = "max_backoff_secs",
+        default = "UpdateRetrySettings::default_max_backoff"
+    )]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_backoff: Duration,
+}
+
+impl UpdateRetrySettings {
+    const fn default_max_attempts() -> u32 {
+        3
+    }
+
+    const fn default_initial_backoff() -> Duration {
+        Duration::from_secs(2)
+    }
+
+    const fn default_backoff_multiplier() -> u32 {
+        2
+    }
+
+    const fn default_max_backoff() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    /// Wait before retrying a step that failed the attempt, or `None` once the step has no
+    /// attempts left.
+    pub fn backoff(&self, failed_attempt: u32) -> Option<Duration> {
+        if self.max_attempts <= failed_attempt {
+            return None;
+        }
+
+        let growth = self
+            .backoff_multiplier
+            .checked_pow(failed_attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let backoff = self.initial_backoff.checked_mul(growth).unwrap_or(self.max_backoff);
+        Some(backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for UpdateRetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            initial_backoff: Self::default_initial_backoff(),
+            backoff_multiplier: Self::default_backoff_multiplier(),
+            max_backoff: Self::default_max_backoff(),
+        }
+    }
+}
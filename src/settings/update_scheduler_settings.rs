@@ -0,0 +1,58 @@
+use serde::Deserialize;
+use serde_with::serde_as;
+use std::time::Duration;
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UpdateSchedulerSettings {
+    /// Turn on the in-process scheduler that periodically requests a weather update from the
+    /// registrar. Default is disabled, leaving updates to external callers.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Cron-like schedule, e.g., "0 */15 * * * *" (sec, min, hour, day of month, month, day of
+    /// week, optional year). When specified, this schedule takes precedence over `interval`.
+    #[serde(default)]
+    pub cron: Option<String>,
+
+    /// Fixed cadence between scheduled updates, used when no cron schedule is specified.
+    #[serde(
+        alias = "interval_secs",
+        default = "UpdateSchedulerSettings::default_interval"
+    )]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub interval: Duration,
+
+    /// Upper bound of a random delay added to each scheduled run, so multiple instances do not
+    /// hit the weather provider in lockstep.
+    #[serde(alias = "jitter_secs", default)]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub jitter: Duration,
+
+    /// Skip a scheduled run of a watchlist while an update of the watchlist is in flight or
+    /// queued.
+    #[serde(default = "UpdateSchedulerSettings::default_skip_if_running")]
+    pub skip_if_running: bool,
+}
+
+impl UpdateSchedulerSettings {
+    const fn default_interval() -> Duration {
+        Duration::from_secs(15 * 60)
+    }
+
+    const fn default_skip_if_running() -> bool {
+        true
+    }
+}
+
+impl Default for UpdateSchedulerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cron: None,
+            interval: Self::default_interval(),
+            jitter: Duration::ZERO,
+            skip_if_running: Self::default_skip_if_running(),
+        }
+    }
+}